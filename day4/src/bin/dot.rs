@@ -0,0 +1,17 @@
+use day4::dot::to_dot;
+use day4::scratchcard::parse_cards;
+
+fn main() -> anyhow::Result<()> {
+    let input = std::fs::read_to_string("input1.txt")?;
+
+    // optionally keep only the first N cards: cargo run --bin dot -- 20 | dot -Tsvg
+    let limit = std::env::args()
+        .nth(1)
+        .map(|n| n.parse::<usize>())
+        .transpose()?;
+
+    let cards = parse_cards(&input)?;
+    print!("{}", to_dot(&cards, limit)?);
+
+    Ok(())
+}
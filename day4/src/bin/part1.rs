@@ -1,4 +1,5 @@
-use day4::part1::process;
+use day4::part1::process_with_rule;
+use day4::scoring::rule_from_name;
 
 const RESULT: usize = 23847;
 
@@ -12,10 +13,16 @@ fn main() -> anyhow::Result<()> {
     // Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
     // Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
 
-    let sum = process(&input)?;
-    println!("Worth points total: {}", sum);
+    // doubling (default), linear or fibonacci
+    let rule_name = std::env::args().nth(1).unwrap_or("doubling".to_string());
+    let rule = rule_from_name(&rule_name)?;
 
-    assert_eq!(sum, RESULT);
+    let sum = process_with_rule(&input, rule.as_ref())?;
+    println!("Worth points total ({}): {}", rule_name, sum);
+
+    if rule_name == "doubling" {
+        assert_eq!(sum, RESULT);
+    }
 
     Ok(())
 }
@@ -0,0 +1,85 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+
+use crate::part2::card_copies;
+use crate::scratchcard::Scratchcard;
+
+// Renders the part2 copy cascade as a graphviz digraph, each card is a node
+// labeled with its final number of instances and has an edge to every card it copies.
+// `limit` keeps only the first N cards, instances are still computed on the whole set.
+pub fn to_dot(cards: &[Scratchcard], limit: Option<usize>) -> Result<String> {
+    let copies = card_copies(cards)?;
+    let shown = limit.unwrap_or(cards.len()).min(cards.len());
+
+    let mut dot = String::new();
+    writeln!(dot, "digraph scratchcards {{")?;
+    writeln!(dot, "    rankdir=LR;")?;
+    writeln!(dot, "    node [shape=box];")?;
+
+    for (card, instances) in cards.iter().zip(copies.iter()).take(shown) {
+        writeln!(
+            dot,
+            "    card{} [label=\"Card {}\\n{} matches\\n{} instances\"];",
+            card.id,
+            card.id,
+            card.matches(),
+            instances
+        )?;
+    }
+
+    for (i, card) in cards.iter().enumerate().take(shown) {
+        let last = (i + card.matches()).min(shown - 1);
+        for next in cards.iter().take(last + 1).skip(i + 1) {
+            writeln!(dot, "    card{} -> card{};", card.id, next.id)?;
+        }
+    }
+
+    if shown < cards.len() {
+        writeln!(
+            dot,
+            "    truncated [shape=plaintext, label=\"... {} more cards\"];",
+            cards.len() - shown
+        )?;
+    }
+
+    writeln!(dot, "}}")?;
+    Ok(dot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scratchcard::parse_cards;
+
+    const INPUT: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn test_to_dot() {
+        let cards = parse_cards(INPUT).unwrap();
+        let dot = to_dot(&cards, None).unwrap();
+
+        assert!(dot.starts_with("digraph scratchcards {"));
+        assert!(dot.contains("card5 [label=\"Card 5\\n0 matches\\n14 instances\"];"));
+        assert!(dot.contains("card1 -> card5;"));
+        assert!(!dot.contains("card1 -> card6;"));
+        assert_eq!(dot.matches("->").count(), 4 + 2 + 2 + 1);
+        assert!(!dot.contains("truncated"));
+    }
+
+    #[test]
+    fn test_to_dot_truncated() {
+        let cards = parse_cards(INPUT).unwrap();
+        let dot = to_dot(&cards, Some(2)).unwrap();
+
+        assert!(dot.contains("card2 [label=\"Card 2\\n2 matches\\n2 instances\"];"));
+        assert!(!dot.contains("card3"));
+        assert_eq!(dot.matches("->").count(), 1);
+        assert!(dot.contains("... 4 more cards"));
+    }
+}
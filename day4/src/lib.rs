@@ -1,3 +1,5 @@
+pub mod dot;
 pub mod part1;
 pub mod part2;
-
+pub mod scoring;
+pub mod scratchcard;
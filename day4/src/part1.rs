@@ -1,58 +1,16 @@
-use std::collections::HashMap;
+use anyhow::{anyhow, Result};
 
-use anyhow::Result;
+use crate::scoring::{Doubling, ScoringRule};
+use crate::scratchcard::parse_cards;
 
 pub fn process(input: &str) -> Result<usize> {
-    let sum = input
-        .lines()
-        .filter_map(|line| {
-            let (winners, numbers) = parse_numbers(line)?;
-            let winners = get_winners(&winners, &numbers);
-            let points = get_points(winners.len());
-            Some(points)
-        })
-        .sum();
-    Ok(sum)
+    process_with_rule(input, &Doubling)
 }
 
-fn parse_numbers(input: &str) -> Option<(Vec<usize>, Vec<usize>)> {
-    // Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-    let (winners, numbers) = input
-        .split_once(':')
-        .and_then(|(_, numbers)| numbers.trim().split_once('|'))?;
-
-    let winners = winners
-        .trim()
-        .split_ascii_whitespace()
-        .map(|v| v.trim().parse::<usize>().ok())
-        .collect::<Option<Vec<usize>>>()?;
-
-    let numbers = numbers
-        .trim()
-        .split_ascii_whitespace()
-        .map(|v| v.trim().parse::<usize>().ok())
-        .collect::<Option<Vec<usize>>>()?;
-
-    Some((winners, numbers))
-}
-
-fn get_winners(winners: &[usize], numbers: &[usize]) -> Vec<usize> {
-    let winners_map = winners
-        .iter()
-        .enumerate()
-        .map(|(i, number)| (*number, i))
-        .collect::<HashMap<usize, usize>>();
-
-    numbers
-        .iter()
-        .filter_map(|v| winners_map.get(v).copied())
-        .collect()
-}
-
-fn get_points(winners_len: usize) -> usize {
-    if winners_len > 0 {
-        1 << (winners_len - 1) // Use bitwise shift to double the points
-    } else {
-        0
-    }
+pub fn process_with_rule(input: &str, rule: &dyn ScoringRule) -> Result<usize> {
+    parse_cards(input)?.iter().try_fold(0usize, |sum, card| {
+        let points = rule.points(card.matches())?;
+        sum.checked_add(points)
+            .ok_or(anyhow!("Total points overflow at card {}", card.id))
+    })
 }
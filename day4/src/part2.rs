@@ -1,63 +1,79 @@
-use std::collections::HashMap;
+use anyhow::{anyhow, Result};
 
-use anyhow::Result;
+use crate::scratchcard::{parse_cards, Scratchcard};
 
 pub fn process(input: &str) -> Result<usize, anyhow::Error> {
-    let lines = input.lines().collect::<Vec<&str>>();
-    let mut card_copies = HashMap::new();
+    let (total_cards, _) = process_with_copies(input)?;
+    Ok(total_cards)
+}
 
-    for (i, _) in lines.iter().enumerate() {
-        card_copies.entry(i + 1).or_insert(1);
-    }
+// Same as process but also returns the number of instances of each card,
+// indexed by card position.
+pub fn process_with_copies(input: &str) -> Result<(usize, Vec<usize>)> {
+    let cards = parse_cards(input)?;
+    let copies = card_copies(&cards)?;
 
-    for (i, line) in lines.iter().enumerate() {
-        let card_id = i + 1;
+    let total_cards = copies.iter().try_fold(0usize, |total, copies| {
+        total
+            .checked_add(*copies)
+            .ok_or(anyhow!("Total number of cards overflows"))
+    })?;
 
-        let (winners, numbers) =
-            parse_numbers(line).ok_or(anyhow::Error::msg("Failed parsing numbers"))?;
-        let val = get_winners(&winners, &numbers).len();
+    Ok((total_cards, copies))
+}
 
-        let current_copies = *card_copies.get(&card_id).unwrap_or(&0);
-        for j in 1..=val {
-            let next_card_id = card_id + j;
-            *card_copies.entry(next_card_id).or_insert(0) += current_copies;
+// Each card wins one copy of the next `matches` cards, for every instance of it.
+// Copies past the last card are ignored, the table never grows beyond the input.
+pub fn card_copies(cards: &[Scratchcard]) -> Result<Vec<usize>> {
+    let mut copies = vec![1usize; cards.len()];
+
+    for (i, card) in cards.iter().enumerate() {
+        let current_copies = copies[i];
+        let last = (i + card.matches()).min(cards.len().saturating_sub(1));
+
+        for next in copies.iter_mut().take(last + 1).skip(i + 1) {
+            *next = next
+                .checked_add(current_copies)
+                .ok_or(anyhow!("Copies of card {} overflow", card.id))?;
         }
     }
 
-    let total_cards = card_copies.values().sum();
-    Ok(total_cards)
+    Ok(copies)
 }
 
-fn parse_numbers(input: &str) -> Option<(Vec<usize>, Vec<usize>)> {
-    // Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-    let (winners, numbers) = input
-        .split_once(':')
-        .and_then(|(_, numbers)| numbers.trim().split_once('|'))?;
-
-    let winners = winners
-        .trim()
-        .split_ascii_whitespace()
-        .map(|v| v.trim().parse::<usize>().ok())
-        .collect::<Option<Vec<usize>>>()?;
-
-    let numbers = numbers
-        .trim()
-        .split_ascii_whitespace()
-        .map(|v| v.trim().parse::<usize>().ok())
-        .collect::<Option<Vec<usize>>>()?;
-
-    Some((winners, numbers))
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn test_process_with_copies() {
+        let (total, copies) = process_with_copies(INPUT).unwrap();
+        assert_eq!(total, 30);
+        assert_eq!(copies, vec![1, 2, 4, 8, 14, 1]);
+    }
 
-fn get_winners(winners: &[usize], numbers: &[usize]) -> Vec<usize> {
-    let winners_map = winners
-        .iter()
-        .enumerate()
-        .map(|(i, number)| (*number, i))
-        .collect::<HashMap<usize, usize>>();
-
-    numbers
-        .iter()
-        .filter_map(|v| winners_map.get(v).copied())
-        .collect()
+    #[test]
+    fn test_copies_past_last_card_are_ignored() {
+        let input = "Card 1: 1 2 3 | 1 2 3
+Card 2: 1 2 3 | 1 2 3";
+        let (total, copies) = process_with_copies(input).unwrap();
+        assert_eq!(copies, vec![1, 2]);
+        assert_eq!(total, 3);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let input = (1..=200)
+            .map(|i| format!("Card {}: 1 2 3 4 5 6 7 8 9 10 | 1 2 3 4 5 6 7 8 9 10", i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(process(&input).is_err());
+    }
 }
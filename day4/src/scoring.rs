@@ -0,0 +1,86 @@
+use anyhow::{anyhow, Result};
+
+// How many points a card is worth given its number of matches.
+// Implementations must return an error instead of overflowing.
+pub trait ScoringRule {
+    fn points(&self, matches: usize) -> Result<usize>;
+}
+
+// The puzzle rule: first match is worth one point,
+// every match after that doubles the points.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Doubling;
+
+impl ScoringRule for Doubling {
+    fn points(&self, matches: usize) -> Result<usize> {
+        if matches == 0 {
+            return Ok(0);
+        }
+        u32::try_from(matches - 1)
+            .ok()
+            .and_then(|shift| 1usize.checked_shl(shift))
+            .ok_or(anyhow!("{} matches overflow doubling points", matches))
+    }
+}
+
+// One point per match.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Linear;
+
+impl ScoringRule for Linear {
+    fn points(&self, matches: usize) -> Result<usize> {
+        Ok(matches)
+    }
+}
+
+// n matches are worth the n-th Fibonacci number: 1, 1, 2, 3, 5, 8, ...
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Fibonacci;
+
+impl ScoringRule for Fibonacci {
+    fn points(&self, matches: usize) -> Result<usize> {
+        let (mut current, mut next) = (0usize, 1usize);
+        for _ in 0..matches {
+            let sum = current
+                .checked_add(next)
+                .ok_or(anyhow!("{} matches overflow fibonacci points", matches))?;
+            current = next;
+            next = sum;
+        }
+        Ok(current)
+    }
+}
+
+pub fn rule_from_name(name: &str) -> Result<Box<dyn ScoringRule>> {
+    match name.to_lowercase().as_str() {
+        "doubling" => Ok(Box::new(Doubling)),
+        "linear" => Ok(Box::new(Linear)),
+        "fibonacci" => Ok(Box::new(Fibonacci)),
+        _ => Err(anyhow!("Unknown scoring rule: {}", name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules() {
+        let points = |rule: &dyn ScoringRule| {
+            (0..=6)
+                .map(|matches| rule.points(matches).unwrap())
+                .collect::<Vec<usize>>()
+        };
+        assert_eq!(points(&Doubling), vec![0, 1, 2, 4, 8, 16, 32]);
+        assert_eq!(points(&Linear), vec![0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(points(&Fibonacci), vec![0, 1, 1, 2, 3, 5, 8]);
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(Doubling.points(64).unwrap(), 1 << 63);
+        assert!(Doubling.points(65).is_err());
+        assert!(Fibonacci.points(200).is_err());
+        assert!(rule_from_name("squared").is_err());
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scratchcard {
+    pub id: usize,
+    pub winning: Vec<usize>,
+    pub have: Vec<usize>,
+}
+
+impl Scratchcard {
+    // returns the numbers we have that are also in the winning list
+    pub fn winners(&self) -> Vec<usize> {
+        let winning = self.winning.iter().collect::<HashSet<_>>();
+
+        self.have
+            .iter()
+            .filter(|v| winning.contains(v))
+            .copied()
+            .collect()
+    }
+
+    pub fn matches(&self) -> usize {
+        self.winners().len()
+    }
+}
+
+impl TryFrom<&str> for Scratchcard {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        // Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+        let (card, numbers) = value
+            .split_once(':')
+            .ok_or(anyhow!("Missing card header in: {}", value))?;
+
+        let id = card
+            .trim()
+            .strip_prefix("Card")
+            .ok_or(anyhow!("Invalid card header: {}", card))?
+            .trim();
+        if id.is_empty() {
+            return Err(anyhow!("Missing card id in: {}", card));
+        }
+        let id = id.parse::<usize>()?;
+
+        let (winning, have) = numbers
+            .split_once('|')
+            .ok_or(anyhow!("Missing '|' separator in card {}", id))?;
+
+        let winning = parse_numbers(winning)?;
+        let have = parse_numbers(have)?;
+
+        let mut seen = HashSet::with_capacity(winning.len());
+        if let Some(duplicated) = winning.iter().find(|v| !seen.insert(**v)) {
+            return Err(anyhow!(
+                "Card {} has duplicated winning number {}",
+                id,
+                duplicated
+            ));
+        }
+
+        Ok(Self { id, winning, have })
+    }
+}
+
+fn parse_numbers(input: &str) -> Result<Vec<usize>> {
+    input
+        .split_ascii_whitespace()
+        .map(|v| v.parse::<usize>().map_err(Into::into))
+        .collect()
+}
+
+// Parses every non blank line as a card, ids must start at 1 and follow the
+// line order as part2 copies cards by id.
+pub fn parse_cards(input: &str) -> Result<Vec<Scratchcard>> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(i, line)| {
+            let card = Scratchcard::try_from(line)?;
+            if card.id != i + 1 {
+                return Err(anyhow!(
+                    "Card id {} does not match its position, expected {}",
+                    card.id,
+                    i + 1
+                ));
+            }
+            Ok(card)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_card() {
+        let card =
+            Scratchcard::try_from("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap();
+        assert_eq!(card.id, 1);
+        assert_eq!(card.winning, vec![41, 48, 83, 86, 17]);
+        assert_eq!(card.have, vec![83, 86, 6, 31, 17, 9, 48, 53]);
+        assert_eq!(card.matches(), 4);
+    }
+
+    #[test]
+    fn test_invalid_cards() {
+        let inputs = [
+            "41 48 | 83 86",
+            "Card : 41 48 | 83 86",
+            "Card x: 41 48 | 83 86",
+            "Card 1: 41 48 83 86",
+            "Card 1: 41 41 | 83 86",
+            "Card 1: 41 ab | 83 86",
+        ];
+        for input in inputs {
+            assert!(Scratchcard::try_from(input).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_ids_follow_line_order() {
+        let input = "Card 1: 41 48 | 83 86
+Card 3: 13 32 | 61 30";
+        assert!(parse_cards(input).is_err());
+
+        let input = "Card 1: 41 48 | 83 86
+Card 1: 13 32 | 61 30";
+        assert!(parse_cards(input).is_err());
+
+        let input = "Card 1: 41 48 | 83 86
+Card 2: 13 32 | 61 30";
+        assert_eq!(parse_cards(input).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_blank_lines() {
+        let cards = parse_cards("Card 1: 1 2 | 1 2\n\n").unwrap();
+        assert_eq!(cards.len(), 1);
+
+        let input = "Card 1: 41 48 | 83 86
+
+Card 2: 13 32 | 61 30
+";
+        assert_eq!(parse_cards(input).unwrap().len(), 2);
+        assert_eq!(crate::part1::process("Card 1: 1 2 | 1 2\n\n").unwrap(), 2);
+    }
+}
@@ -0,0 +1,20 @@
+// Deterministic xorshift generator, the tests and benches draw the same
+// values on every run. Kept out of the library, the unit tests include this
+// file as a module of their own.
+pub struct XorShift(u64);
+
+impl Default for XorShift {
+    fn default() -> Self {
+        Self(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+impl XorShift {
+    // Next value in 0..max.
+    pub fn below(&mut self, max: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % max as u64) as usize
+    }
+}
@@ -0,0 +1,67 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use day5::almanac::{Map, Range};
+
+mod common;
+
+use common::XorShift;
+
+// Non overlapping ranges in shuffled order, like the puzzle input.
+fn ranges(count: usize) -> Vec<Range> {
+    let mut random = XorShift::default();
+    let mut ranges = (0..count)
+        .map(|i| {
+            let dest = random.below(1_000_000_000);
+            (dest, i * 1_000, 500 + random.below(500)).into()
+        })
+        .collect::<Vec<Range>>();
+
+    for i in (1..ranges.len()).rev() {
+        ranges.swap(i, random.below(i + 1));
+    }
+    ranges
+}
+
+// The lookup Map::transpose used before ranges were sorted.
+fn transpose_linear(ranges: &[Range], src: usize) -> usize {
+    ranges
+        .iter()
+        .find_map(|range| range.src_dst(src))
+        .unwrap_or(src)
+}
+
+fn bench_transpose(c: &mut Criterion) {
+    let mut group = c.benchmark_group("transpose");
+
+    for count in [100, 1_000, 5_000] {
+        let ranges = ranges(count);
+        let map = Map::new(ranges.clone());
+        let sources = (0..count * 1_000).step_by(97).collect::<Vec<usize>>();
+
+        group.bench_with_input(BenchmarkId::new("linear", count), &sources, |b, sources| {
+            b.iter(|| {
+                sources
+                    .iter()
+                    .map(|src| transpose_linear(&ranges, black_box(*src)))
+                    .collect::<Vec<usize>>()
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("binary", count), &sources, |b, sources| {
+            b.iter(|| {
+                sources
+                    .iter()
+                    .map(|src| map.transpose(black_box(*src)))
+                    .collect::<Vec<usize>>()
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("sorted", count), &sources, |b, sources| {
+            b.iter(|| map.transpose_sorted(black_box(sources)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_transpose);
+criterion_main!(benches);
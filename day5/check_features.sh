@@ -0,0 +1,10 @@
+#!/bin/sh
+# Builds, lints and tests day5 under every combination of its features.
+set -e
+cd "$(dirname "$0")"
+
+for features in "" "parallel" "progress" "parallel,progress"; do
+    echo "== features: ${features:-none}"
+    cargo clippy --no-default-features --features "$features" --all-targets -- -D warnings
+    cargo test --no-default-features --features "$features"
+done
@@ -0,0 +1,685 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range as StdRange;
+
+use anyhow::{anyhow, Result};
+
+use crate::category::CategoryGraph;
+use crate::validation;
+
+// The parsed puzzle input, seeds plus the maps leading from seed to location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Almanac {
+    pub seeds: Vec<usize>,
+    // seed -> location chain, in the order the maps must be applied
+    pub maps: Vec<Map>,
+    // categories along the chain, `seed` first and `location` last
+    pub categories: Vec<String>,
+}
+
+impl Almanac {
+    pub fn parse(input: &str) -> Result<Self> {
+        let seeds = parse_seeds(input)?;
+        let graph = CategoryGraph::parse(input)?;
+        let path = graph.path("seed", "location")?;
+
+        let categories = std::iter::once("seed".to_string())
+            .chain(path.iter().map(|edge| edge.to.clone()))
+            .collect();
+        let maps = path.into_iter().map(|edge| edge.map.clone()).collect();
+
+        Ok(Self {
+            seeds,
+            maps,
+            categories,
+        })
+    }
+
+    // Like parse but refuses almanacs with overlapping, empty or overflowing ranges.
+    pub fn parse_strict(input: &str) -> Result<Self> {
+        validation::check(input)?;
+        Self::parse(input)
+    }
+
+    // Part 1, every number is a seed.
+    pub fn seeds_as_values(&self) -> &[usize] {
+        &self.seeds
+    }
+
+    // Part 2, numbers come in pairs of range start and length.
+    pub fn seeds_as_ranges(&self) -> Result<Vec<StdRange<usize>>> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err(anyhow!("Seed ranges must come in start and length pairs"));
+        }
+
+        self.seeds
+            .chunks(2)
+            .map(|pair| {
+                let end = pair[0].checked_add(pair[1]).ok_or(anyhow!(
+                    "Seed range {} {} overflows",
+                    pair[0],
+                    pair[1]
+                ))?;
+                Ok(pair[0]..end)
+            })
+            .collect()
+    }
+
+    pub fn location(&self, seed: usize) -> usize {
+        self.maps
+            .iter()
+            .fold(seed, |value, map| map.transpose(value))
+    }
+
+    // Lowest location reached by any seed inside the given ranges.
+    pub fn lowest_location(&self, seeds: Vec<StdRange<usize>>) -> Option<usize> {
+        self.maps
+            .iter()
+            .fold(seeds, |ranges, map| map.transpose_ranges(ranges))
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .map(|range| range.start)
+            .min()
+    }
+
+    // Part 1 seed ending at the lowest location.
+    pub fn winning_seed(&self) -> Option<usize> {
+        self.seeds
+            .iter()
+            .copied()
+            .min_by_key(|seed| self.location(*seed))
+    }
+
+    // Part 2 seed ending at the lowest location, found walking back
+    // from that location and keeping the first seed inside the seed ranges.
+    pub fn winning_seed_in_ranges(&self) -> Result<Option<usize>> {
+        let seeds = self.seeds_as_ranges()?;
+        let Some(lowest) = self.lowest_location(seeds.clone()) else {
+            return Ok(None);
+        };
+
+        let winner = seeds_for_locations(&self.maps, lowest..lowest + 1)
+            .iter()
+            .flat_map(|candidates| {
+                seeds.iter().filter_map(|seeds| {
+                    let start = candidates.start.max(seeds.start);
+                    (start < candidates.end.min(seeds.end)).then_some(start)
+                })
+            })
+            .min();
+
+        Ok(winner)
+    }
+}
+
+fn parse_seeds(input: &str) -> Result<Vec<usize>> {
+    let line = input
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .ok_or(anyhow!("Invalid input"))?;
+
+    let numbers = line
+        .strip_prefix("seeds:")
+        .ok_or(anyhow!("invalid seeds inputs"))?;
+
+    numbers
+        .split_ascii_whitespace()
+        .map(|s| {
+            s.parse::<usize>()
+                .map_err(|_| anyhow!("Invalid seed: {}", s))
+        })
+        .collect()
+}
+
+// Ranges are kept sorted by source and without overlaps so lookups can binary search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Map(Vec<Range>);
+
+impl Map {
+    // Overlapping ranges are clipped so the first one in input order keeps
+    // the overlap, same result as the linear find_map lookup.
+    pub fn new(map: Vec<Range>) -> Map {
+        let mut sorted: BTreeMap<usize, Range> = BTreeMap::new();
+
+        for (index, mut range) in map.into_iter().enumerate() {
+            if range.src.is_empty() {
+                continue;
+            }
+            range.written.index = index;
+
+            let mut start = range.src.start;
+            // the previous range might start before ours and still cover it
+            let previous = sorted
+                .range(..range.src.start)
+                .next_back()
+                .map(|(_, previous)| previous.src.end);
+            if let Some(previous_end) = previous {
+                start = start.max(previous_end);
+            }
+
+            let covered = sorted
+                .range(range.src.start..range.src.end)
+                .map(|(_, covered)| covered.src.clone())
+                .collect::<Vec<_>>();
+
+            for covered in covered.into_iter() {
+                if start < covered.start {
+                    let clipped = range.clip(start..covered.start);
+                    sorted.insert(clipped.src.start, clipped);
+                }
+                start = start.max(covered.end);
+            }
+            if start < range.src.end {
+                let clipped = range.clip(start..range.src.end);
+                sorted.insert(clipped.src.start, clipped);
+            }
+        }
+
+        Self(sorted.into_values().collect())
+    }
+
+    pub fn transpose(&self, src: usize) -> usize {
+        self.find(src)
+            .and_then(|(_, range)| range.src_dst(src))
+            .unwrap_or(src)
+    }
+
+    // The range containing `src` and its index in the sorted ranges,
+    // None means the value falls back to the identity.
+    pub fn find(&self, src: usize) -> Option<(usize, &Range)> {
+        let i = self.0.partition_point(|range| range.src.end <= src);
+        self.0
+            .get(i)
+            .filter(|range| range.contains_src(src))
+            .map(|range| (i, range))
+    }
+
+    // Transposes a sorted slice of values in a single pass over the ranges.
+    pub fn transpose_sorted(&self, sources: &[usize]) -> Vec<usize> {
+        debug_assert!(sources.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        let mut ranges = self.0.iter().peekable();
+        sources
+            .iter()
+            .map(|&src| {
+                while ranges.next_if(|range| range.src.end <= src).is_some() {}
+                ranges
+                    .peek()
+                    .and_then(|range| range.src_dst(src))
+                    .unwrap_or(src)
+            })
+            .collect()
+    }
+
+    // Maps a set of source intervals at once, every input is split against
+    // the ranges' src intervals, whatever no range claims is kept as is.
+    pub fn transpose_ranges(&self, ranges: Vec<StdRange<usize>>) -> Vec<StdRange<usize>> {
+        let mut pending = ranges;
+        let mut mapped = Vec::with_capacity(pending.len());
+
+        for range in self.0.iter() {
+            let mut unmapped = Vec::with_capacity(pending.len());
+            for input in pending {
+                let (overlap, rest) = range.split(input);
+                mapped.extend(overlap);
+                unmapped.extend(rest);
+            }
+            pending = unmapped;
+        }
+
+        mapped.extend(pending);
+        mapped
+    }
+
+    // Composes two maps into one, `a.compose(&b).transpose(x)` equals
+    // `b.transpose(a.transpose(x))`. Only non identity pieces are kept as ranges.
+    pub fn compose(&self, next: &Map) -> Map {
+        let next_pieces = next.pieces();
+        let mut ranges = Vec::new();
+
+        for (src, dest) in self.pieces() {
+            let image = dest..dest + (src.end - src.start);
+            let first = next_pieces.partition_point(|(next_src, _)| next_src.end <= image.start);
+
+            for (next_src, next_dest) in next_pieces[first..].iter() {
+                if next_src.start >= image.end {
+                    break;
+                }
+                let start = image.start.max(next_src.start);
+                let end = image.end.min(next_src.end);
+
+                let composed_src = src.start + (start - dest);
+                let composed_dest = next_dest + (start - next_src.start);
+                if composed_src != composed_dest {
+                    ranges.push((composed_dest, composed_src, end - start).into());
+                }
+            }
+        }
+
+        Map::new(ranges)
+    }
+
+    // Folds the whole chain into a single seed -> location map.
+    pub fn compose_all(maps: &[Map]) -> Map {
+        maps.iter()
+            .fold(Map::new(Vec::new()), |composed, map| composed.compose(map))
+    }
+
+    // Inverse of transpose, returns every source value that ends up at `dst`.
+    // There can be several when an identity gap and ranges land on the same value.
+    pub fn transpose_back(&self, dst: usize) -> Vec<usize> {
+        let mut sources = self
+            .0
+            .iter()
+            .filter(|range| range.contains_dst(dst))
+            .map(|range| range.src.start + (dst - range.dest.start))
+            .chain(std::iter::once(dst))
+            // ranges can shadow the identity, keep real preimages only
+            .filter(|src| self.transpose(*src) == dst)
+            .collect::<Vec<usize>>();
+
+        sources.sort();
+        sources.dedup();
+        sources
+    }
+
+    // Inverse of transpose_ranges, returns the source intervals that
+    // end up inside any of the given destination intervals.
+    pub fn transpose_ranges_back(&self, ranges: &[StdRange<usize>]) -> Vec<StdRange<usize>> {
+        let mut sources = Vec::new();
+
+        for (src, dest) in self.pieces() {
+            let image = dest..dest + (src.end - src.start);
+            for range in ranges.iter() {
+                let start = image.start.max(range.start);
+                let end = image.end.min(range.end);
+                if start < end {
+                    sources.push(src.start + (start - dest)..src.start + (end - dest));
+                }
+            }
+        }
+
+        merge_ranges(sources)
+    }
+
+    pub fn ranges(&self) -> &[Range] {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // Splits the whole domain into (source interval, destination start) pieces
+    // sorted by source, gaps between ranges are identity pieces.
+    fn pieces(&self) -> Vec<(StdRange<usize>, usize)> {
+        let mut pieces = Vec::with_capacity(self.0.len() * 2 + 1);
+        let mut start = 0;
+
+        for range in self.0.iter() {
+            if start < range.src.start {
+                pieces.push((start..range.src.start, start));
+            }
+            pieces.push((range.src.clone(), range.dest.start));
+            start = range.src.end;
+        }
+        if start < usize::MAX {
+            pieces.push((start..usize::MAX, start));
+        }
+
+        pieces
+    }
+}
+
+impl fmt::Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for range in self.0.iter() {
+            writeln!(
+                f,
+                "{}..{} -> {}..{}",
+                range.src.start, range.src.end, range.dest.start, range.dest.end
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// Which seeds end up at a location inside `locations`, walking the chain backwards.
+pub fn seeds_for_locations(maps: &[Map], locations: StdRange<usize>) -> Vec<StdRange<usize>> {
+    maps.iter().rev().fold(vec![locations], |ranges, map| {
+        map.transpose_ranges_back(&ranges)
+    })
+}
+
+// Sorts and joins overlapping or adjacent intervals.
+fn merge_ranges(mut ranges: Vec<StdRange<usize>>) -> Vec<StdRange<usize>> {
+    ranges.retain(|range| !range.is_empty());
+    ranges.sort_by_key(|range| range.start);
+
+    let mut merged: Vec<StdRange<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+// A range line as written in the input, index is its position inside the map.
+// Sorting and clipping inside Map::new never change it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Written {
+    pub index: usize,
+    pub dest: usize,
+    pub src: usize,
+    pub len: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Range {
+    dest: StdRange<usize>,
+    src: StdRange<usize>,
+    written: Written,
+}
+
+// Ranges are equal when they move the same values, wherever they were written.
+impl PartialEq for Range {
+    fn eq(&self, other: &Self) -> bool {
+        self.dest == other.dest && self.src == other.src
+    }
+}
+
+impl Eq for Range {}
+
+// A range reaching past usize::MAX is cut where the first of its bounds
+// saturates, so dest and src keep the same length and moving a value never
+// overflows. validation::validate reports those ranges.
+impl From<(usize, usize, usize)> for Range {
+    fn from((dest, src, len): (usize, usize, usize)) -> Self {
+        let written = Written {
+            index: 0,
+            dest,
+            src,
+            len,
+        };
+        let len = len.min(usize::MAX - dest).min(usize::MAX - src);
+        Self {
+            dest: (dest..dest + len),
+            src: (src..src + len),
+            written,
+        }
+    }
+}
+
+impl Range {
+    pub fn new(range: (usize, usize, usize)) -> Self {
+        range.into()
+    }
+
+    pub fn src(&self) -> &StdRange<usize> {
+        &self.src
+    }
+
+    pub fn dest(&self) -> &StdRange<usize> {
+        &self.dest
+    }
+
+    pub fn written(&self) -> &Written {
+        &self.written
+    }
+
+    pub fn contains_src(&self, source: usize) -> bool {
+        self.src.contains(&source)
+    }
+
+    pub fn contains_dst(&self, dst: usize) -> bool {
+        self.dest.contains(&dst)
+    }
+
+    pub fn src_dst(&self, source: usize) -> Option<usize> {
+        if !self.contains_src(source) {
+            return None;
+        }
+
+        let offset = source - self.src.start;
+        Some(self.dest.start + offset)
+    }
+
+    // Sub range covering only `src`, which must be inside the source interval.
+    fn clip(&self, src: StdRange<usize>) -> Range {
+        let offset = src.start - self.src.start;
+        let clipped: Range = (self.dest.start + offset, src.start, src.end - src.start).into();
+        Range {
+            written: self.written,
+            ..clipped
+        }
+    }
+
+    // Splits `input` against the source interval, returns the overlapping
+    // part already moved to dest and the non-overlapping leftovers.
+    fn split(&self, input: StdRange<usize>) -> (Option<StdRange<usize>>, Vec<StdRange<usize>>) {
+        let start = input.start.max(self.src.start);
+        let end = input.end.min(self.src.end);
+
+        if start >= end {
+            return (None, vec![input]);
+        }
+
+        let overlap =
+            (start - self.src.start + self.dest.start)..(end - self.src.start + self.dest.start);
+        let rest = [input.start..start, end..input.end]
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .collect();
+
+        (Some(overlap), rest)
+    }
+}
+
+// The example almanac of the puzzle, shared by the tests of every module.
+#[cfg(test)]
+pub(crate) const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transpose_linear(ranges: &[Range], src: usize) -> usize {
+        ranges
+            .iter()
+            .find_map(|range| range.src_dst(src))
+            .unwrap_or(src)
+    }
+
+    #[test]
+    fn test_parse() {
+        let almanac = Almanac::parse(EXAMPLE).unwrap();
+        assert_eq!(almanac.seeds_as_values(), &[79, 14, 55, 13]);
+        assert_eq!(almanac.seeds_as_ranges().unwrap(), vec![79..93, 55..68]);
+        assert_eq!(almanac.maps.len(), 7);
+        assert_eq!(almanac.categories.first().unwrap(), "seed");
+        assert_eq!(almanac.categories.last().unwrap(), "location");
+        assert_eq!(almanac.location(79), 82);
+
+        assert!(Almanac::parse("seeds: 1 x\n\nseed-to-location map:\n1 2 3").is_err());
+        assert!(Almanac::parse("seeds: 1\n\nseed-to-soil map:\n1 2 3").is_err());
+        let odd = Almanac::parse("seeds: 1 2 3\n\nseed-to-location map:\n1 2 3").unwrap();
+        assert!(odd.seeds_as_ranges().is_err());
+    }
+
+    #[test]
+    fn test_winning_seeds() {
+        let almanac = Almanac::parse(EXAMPLE).unwrap();
+        assert_eq!(almanac.winning_seed(), Some(13));
+        assert_eq!(almanac.location(13), 35);
+
+        let seed = almanac.winning_seed_in_ranges().unwrap().unwrap();
+        assert_eq!(seed, 82);
+        assert_eq!(almanac.location(seed), 46);
+    }
+
+    #[test]
+    fn test_map_is_sorted() {
+        let ranges: Vec<Range> = vec![
+            (100, 10, 10).into(),
+            (200, 5, 10).into(),
+            (300, 0, 40).into(),
+            (400, 12, 2).into(),
+            (500, 50, 0).into(),
+        ];
+        let map = Map::new(ranges.clone());
+
+        assert!(map
+            .ranges()
+            .windows(2)
+            .all(|pair| pair[0].src.end <= pair[1].src.start));
+
+        let sources = (0..60).collect::<Vec<usize>>();
+        let expected = sources
+            .iter()
+            .map(|src| transpose_linear(&ranges, *src))
+            .collect::<Vec<usize>>();
+        let single = sources
+            .iter()
+            .map(|src| map.transpose(*src))
+            .collect::<Vec<usize>>();
+
+        assert_eq!(single, expected);
+        assert_eq!(map.transpose_sorted(&sources), expected);
+    }
+
+    #[test]
+    fn test_transpose_ranges() {
+        let map = Map::new(vec![(50, 98, 2).into(), (52, 50, 48).into()]);
+        let mut ranges = map.transpose_ranges(vec![40..60, 97..101]);
+        ranges.sort_by_key(|range| range.start);
+        assert_eq!(ranges, vec![40..50, 50..52, 52..62, 99..100, 100..101]);
+    }
+
+    #[test]
+    fn test_compose_all() {
+        let almanac = Almanac::parse(EXAMPLE).unwrap();
+        let composed = Map::compose_all(&almanac.maps);
+
+        for seed in 0..200 {
+            assert_eq!(
+                composed.transpose(seed),
+                almanac.location(seed),
+                "seed {}",
+                seed
+            );
+        }
+        assert!(composed.to_string().lines().count() == composed.len());
+    }
+
+    #[test]
+    fn test_compose_overlapping_ranges() {
+        // second range overlaps the first one, the first match must win
+        let first = Map::new(vec![(100, 10, 10).into(), (200, 5, 10).into()]);
+        let second = Map::new(vec![(0, 100, 5).into()]);
+        let composed = first.compose(&second);
+
+        for value in 0..300 {
+            assert_eq!(
+                composed.transpose(value),
+                second.transpose(first.transpose(value)),
+                "value {}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_saturated_range() {
+        let range = Range::from((usize::MAX - 1, 0, 10));
+        assert_eq!(range.dest(), &(usize::MAX - 1..usize::MAX));
+        assert_eq!(range.src(), &(0..1));
+        assert_eq!(range.src_dst(0), Some(usize::MAX - 1));
+
+        let input = "seeds: 5 2
+
+seed-to-location map:
+18446744073709551614 0 10";
+        assert_eq!(crate::part1::process(input).unwrap(), 2);
+        assert_eq!(crate::part2::process(input).unwrap(), 5);
+        assert!(crate::part1::process_strict(input).is_err());
+
+        let input = "seeds: 0 2
+
+seed-to-location map:
+18446744073709551614 0 10";
+        assert_eq!(crate::part1::process(input).unwrap(), 2);
+        assert_eq!(crate::part2::process(input).unwrap(), 1);
+        assert_eq!(
+            crate::part2::process_brute_force(input).unwrap(),
+            crate::part2::process(input).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_transpose_back() {
+        // 5 is reached from the identity and from 20, 10 is shadowed by the second range
+        let map = Map::new(vec![(0, 15, 10).into(), (100, 10, 5).into()]);
+        assert_eq!(map.transpose_back(5), vec![5, 20]);
+        assert_eq!(map.transpose_back(10), vec![]);
+        assert_eq!(map.transpose_back(102), vec![12, 102]);
+        assert_eq!(map.transpose_back(300), vec![300]);
+
+        for dst in 0..150 {
+            let expected = (0..150)
+                .filter(|src| map.transpose(*src) == dst)
+                .collect::<Vec<usize>>();
+            assert_eq!(map.transpose_back(dst), expected, "dst {}", dst);
+        }
+    }
+
+    #[test]
+    fn test_seeds_for_locations() {
+        let almanac = Almanac::parse(EXAMPLE).unwrap();
+        let seeds = seeds_for_locations(&almanac.maps, 40..60);
+
+        for seed in 0..200 {
+            let found = seeds.iter().any(|range| range.contains(&seed));
+            let location = almanac.location(seed);
+            assert_eq!(found, (40..60).contains(&location), "seed {}", seed);
+        }
+    }
+}
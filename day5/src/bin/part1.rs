@@ -1,11 +1,15 @@
-use day5::part1::process;
+use day5::part1::{process, process_strict};
 
 const RESULT: usize = 175622908;
 
 fn main() -> anyhow::Result<()> {
     let input = std::fs::read_to_string("input1.txt")?;
 
-    let lowest = process(&input)?;
+    let lowest = if std::env::args().any(|arg| arg == "--strict") {
+        process_strict(&input)?
+    } else {
+        process(&input)?
+    };
     println!("lowest: {}", lowest);
 
     assert_eq!(lowest, RESULT);
@@ -1,15 +1,15 @@
-use day5::part2::process;
+use day5::part2::{process, process_strict};
 
 const RESULT: usize = 5200543;
 
 fn main() -> anyhow::Result<()> {
     let input = std::fs::read_to_string("input1.txt")?;
-    rayon::ThreadPoolBuilder::new()
-        .num_threads(10)
-        .build_global()
-        .unwrap();
 
-    let lowest = process(&input)?;
+    let lowest = if std::env::args().any(|arg| arg == "--strict") {
+        process_strict(&input)?
+    } else {
+        process(&input)?
+    };
     println!("lowest: {}", lowest);
 
     assert_eq!(lowest, RESULT);
@@ -0,0 +1,19 @@
+use anyhow::anyhow;
+use day5::almanac::Almanac;
+
+// cargo run --bin trace -- <seed|winner|winner-ranges>
+fn main() -> anyhow::Result<()> {
+    let input = std::fs::read_to_string("input1.txt")?;
+    let almanac = Almanac::parse(&input)?;
+
+    let seed = match std::env::args().nth(1).as_deref() {
+        None | Some("winner") => almanac.winning_seed(),
+        Some("winner-ranges") => almanac.winning_seed_in_ranges()?,
+        Some(seed) => Some(seed.parse::<usize>()?),
+    }
+    .ok_or(anyhow!("No seeds to trace"))?;
+
+    print!("{}", almanac.trace(seed));
+
+    Ok(())
+}
@@ -0,0 +1,240 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use anyhow::{anyhow, Result};
+
+use crate::almanac::{Map, Range};
+
+// A map between two categories, parsed from a `<from>-to-<to> map:` block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    pub map: Map,
+}
+
+// Every map of the almanac keyed by its source and destination categories,
+// so values can be converted between any two connected categories.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryGraph {
+    edges: Vec<Edge>,
+}
+
+impl CategoryGraph {
+    pub fn new(edges: Vec<Edge>) -> Result<Self> {
+        let mut seen = HashSet::new();
+        if let Some(edge) = edges
+            .iter()
+            .find(|edge| !seen.insert((edge.from.as_str(), edge.to.as_str())))
+        {
+            return Err(anyhow!("Duplicated map {}-to-{}", edge.from, edge.to));
+        }
+
+        let graph = Self { edges };
+        graph.check_cycles()?;
+        Ok(graph)
+    }
+
+    pub fn parse(input: &str) -> Result<Self> {
+        let edges = parse_blocks(input)?
+            .into_iter()
+            .map(|block| Edge {
+                from: block.from,
+                to: block.to,
+                map: Map::new(block.ranges.into_iter().map(Range::from).collect()),
+            })
+            .collect();
+
+        Self::new(edges)
+    }
+
+    pub fn edges(&self) -> &[Edge] {
+        &self.edges
+    }
+
+    // Shortest chain of maps leading from one category to the other,
+    // categories are case insensitive like the map headers.
+    pub fn path(&self, from: &str, to: &str) -> Result<Vec<&Edge>> {
+        let (from, to) = (from.to_lowercase(), to.to_lowercase());
+        let (from, to) = (from.as_str(), to.as_str());
+        let mut previous: HashMap<&str, &Edge> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(category) = queue.pop_front() {
+            if category == to {
+                break;
+            }
+            for edge in self.edges.iter().filter(|edge| edge.from == category) {
+                if edge.to != from && !previous.contains_key(edge.to.as_str()) {
+                    previous.insert(&edge.to, edge);
+                    queue.push_back(&edge.to);
+                }
+            }
+        }
+
+        let mut path = Vec::new();
+        let mut category = to;
+        while category != from {
+            let edge =
+                previous
+                    .get(category)
+                    .ok_or(anyhow!("No maps lead from {} to {}", from, to))?;
+            path.push(*edge);
+            category = &edge.from;
+        }
+        path.reverse();
+
+        Ok(path)
+    }
+
+    pub fn convert(&self, value: usize, from: &str, to: &str) -> Result<usize> {
+        let path = self.path(from, to)?;
+        Ok(path
+            .iter()
+            .fold(value, |value, edge| edge.map.transpose(value)))
+    }
+
+    fn check_cycles(&self) -> Result<()> {
+        // depth first search, a category found again while still on the stack closes a cycle
+        fn visit<'a>(
+            graph: &'a CategoryGraph,
+            category: &'a str,
+            on_stack: &mut Vec<&'a str>,
+            done: &mut HashSet<&'a str>,
+        ) -> Result<()> {
+            if done.contains(category) {
+                return Ok(());
+            }
+            if on_stack.contains(&category) {
+                on_stack.push(category);
+                return Err(anyhow!("Maps form a cycle: {}", on_stack.join(" -> ")));
+            }
+
+            on_stack.push(category);
+            for edge in graph.edges.iter().filter(|edge| edge.from == category) {
+                visit(graph, &edge.to, on_stack, done)?;
+            }
+            on_stack.pop();
+            done.insert(category);
+
+            Ok(())
+        }
+
+        let mut done = HashSet::new();
+        for edge in self.edges.iter() {
+            visit(self, &edge.from, &mut Vec::new(), &mut done)?;
+        }
+        Ok(())
+    }
+}
+
+// A map block as written in the input, before its ranges become a Map.
+pub(crate) struct Block {
+    pub from: String,
+    pub to: String,
+    // dest, src and len of every line, in input order
+    pub ranges: Vec<(usize, usize, usize)>,
+}
+
+pub(crate) fn parse_blocks(input: &str) -> Result<Vec<Block>> {
+    let mut blocks: Vec<Block> = Vec::new();
+
+    for line in input.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("seeds:") {
+            continue;
+        }
+
+        if let Some(header) = line.strip_suffix("map:") {
+            let (from, to) = header
+                .trim()
+                .split_once("-to-")
+                .ok_or(anyhow!("Invalid map header: {}", line))?;
+            if from.is_empty() || to.is_empty() {
+                return Err(anyhow!("Invalid map header: {}", line));
+            }
+
+            blocks.push(Block {
+                from: from.to_lowercase(),
+                to: to.to_lowercase(),
+                ranges: Vec::new(),
+            });
+        } else if let Some(block) = blocks.last_mut() {
+            block.ranges.push(parse_values(line)?);
+        } else {
+            return Err(anyhow!("Range outside of a map: {}", line));
+        }
+    }
+
+    Ok(blocks)
+}
+
+// Parses a `dest src len` range line.
+fn parse_values(line: &str) -> Result<(usize, usize, usize)> {
+    let values = line
+        .split_ascii_whitespace()
+        .map(|s| s.parse::<usize>())
+        .collect::<Result<Vec<usize>, _>>()?;
+
+    match values.as_slice() {
+        [dest, src, len] => Ok((*dest, *src, *len)),
+        _ => Err(anyhow!("Invalid range, expected 3 values: {}", line)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::almanac::EXAMPLE;
+
+    #[test]
+    fn test_convert() {
+        let graph = CategoryGraph::parse(EXAMPLE).unwrap();
+
+        assert_eq!(graph.convert(79, "seed", "soil").unwrap(), 81);
+        assert_eq!(graph.convert(79, "seed", "fertilizer").unwrap(), 81);
+        assert_eq!(graph.convert(14, "seed", "location").unwrap(), 43);
+        assert_eq!(graph.convert(14, "seed", "seed").unwrap(), 14);
+
+        let path = graph.path("temperature", "location").unwrap();
+        let path = path.iter().map(|edge| edge.to.as_str()).collect::<Vec<_>>();
+        assert_eq!(path, vec!["humidity", "location"]);
+
+        // the maps are linked by name, not by the order of the blocks
+        let mut blocks = EXAMPLE.split("\n\n").skip(1).collect::<Vec<_>>();
+        blocks.reverse();
+        let reversed = CategoryGraph::parse(&blocks.join("\n\n")).unwrap();
+        assert_eq!(reversed.convert(14, "seed", "location").unwrap(), 43);
+    }
+
+    #[test]
+    fn test_case_insensitive_categories() {
+        let graph = CategoryGraph::parse("Seed-to-Soil map:\n50 98 2\n52 50 48").unwrap();
+        assert_eq!(graph.convert(79, "Seed", "Soil").unwrap(), 81);
+        assert_eq!(graph.convert(79, "SEED", "soil").unwrap(), 81);
+        assert_eq!(graph.path("seed", "SOIL").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_missing_link() {
+        let graph = CategoryGraph::parse(EXAMPLE).unwrap();
+        assert!(graph.convert(1, "location", "seed").is_err());
+
+        let graph = CategoryGraph::parse("seed-to-soil map:\n50 98 2").unwrap();
+        assert!(graph.convert(1, "seed", "water").is_err());
+    }
+
+    #[test]
+    fn test_invalid_graphs() {
+        let duplicated = "a-to-b map:\n1 2 3\n\na-to-b map:\n4 5 6";
+        assert!(CategoryGraph::parse(duplicated).is_err());
+
+        let cycle = "a-to-b map:\n1 2 3\n\nb-to-c map:\n1 2 3\n\nc-to-a map:\n1 2 3";
+        assert!(CategoryGraph::parse(cycle).is_err());
+
+        let invalid_range = "a-to-b map:\n1 2";
+        assert!(CategoryGraph::parse(invalid_range).is_err());
+
+        let invalid_header = "a-b map:\n1 2 3";
+        assert!(CategoryGraph::parse(invalid_header).is_err());
+    }
+}
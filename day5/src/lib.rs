@@ -1,3 +1,10 @@
+pub mod almanac;
+pub mod category;
 pub mod part1;
 pub mod part2;
+pub mod trace;
+pub mod validation;
 
+#[cfg(test)]
+#[path = "../benches/common/mod.rs"]
+mod common;
@@ -1,164 +1,144 @@
-use indicatif::ParallelProgressIterator;
-use rayon::prelude::*;
-use std::collections::HashMap;
 use std::ops::Range as StdRange;
 
 use anyhow::{anyhow, Result};
 
-#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
-#[repr(u8)]
-pub enum MapKey {
-    SeedToSoil = 0,
-    SoilToFertilizer,
-    FertilizerToWater,
-    WaterToLight,
-    LightToTemperature,
-    TemperatureToHumidity,
-    HumidityToLocation,
+use crate::almanac::Almanac;
+
+pub fn process(input: &str) -> Result<usize> {
+    solve(&Almanac::parse(input)?)
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Map(Vec<Range>);
+// Same as process on an Almanac::parse_strict almanac.
+pub fn process_strict(input: &str) -> Result<usize> {
+    solve(&Almanac::parse_strict(input)?)
+}
 
-impl Map {
-    pub fn new(map: Vec<Range>) -> Map {
-        Self(map)
-    }
+fn solve(almanac: &Almanac) -> Result<usize> {
+    let seeds = almanac.seeds_as_ranges()?;
 
-    pub fn transpose(&self, src: usize) -> usize {
-        self.0
-            .iter()
-            .find_map(|range| range.src_dst(src))
-            .unwrap_or(src)
-    }
+    let lowest = almanac
+        .lowest_location(seeds)
+        .ok_or(anyhow!("No seeds to locate"))?;
 
-    fn len(&self) -> usize {
-        self.0.len()
-    }
+    Ok(lowest)
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Range {
-    dest: StdRange<usize>,
-    src: StdRange<usize>,
-}
+// Expands every seed range and maps seeds one by one, kept to cross check process.
+pub fn process_brute_force(input: &str) -> Result<usize> {
+    let almanac = Almanac::parse(input)?;
+    let seeds = almanac.seeds_as_ranges()?;
 
-impl From<(usize, usize, usize)> for Range {
-    fn from(value: (usize, usize, usize)) -> Self {
-        Self {
-            dest: (value.0..value.0 + value.2),
-            src: (value.1..value.1 + value.2),
-        }
-    }
+    brute_force_lowest(&almanac, seeds).ok_or_else(|| anyhow!("No seeds to locate"))
 }
 
-impl Range {
-    pub fn new(range: (usize, usize, usize)) -> Self {
-        range.into()
-    }
-
-    pub fn contains_src(&self, source: usize) -> bool {
-        self.src.contains(&source)
-    }
+// Runs on rayon with the `parallel` feature and draws a progress bar with
+// `progress`, the features only pick the iterator and its wrapper.
+fn brute_force_lowest(almanac: &Almanac, seeds: Vec<StdRange<usize>>) -> Option<usize> {
+    #[cfg(all(feature = "parallel", feature = "progress"))]
+    use indicatif::ParallelProgressIterator;
+    #[cfg(all(not(feature = "parallel"), feature = "progress"))]
+    use indicatif::ProgressIterator;
+    #[cfg(feature = "parallel")]
+    use rayon::prelude::*;
+
+    #[cfg(feature = "progress")]
+    let count: u64 = seeds
+        .iter()
+        .map(|range| (range.end - range.start) as u64)
+        .sum();
 
-    pub fn contains_dst(&self, dst: usize) -> bool {
-        self.dest.contains(&dst)
-    }
+    #[cfg(feature = "parallel")]
+    let seeds = seeds.into_par_iter().flat_map(|range| range);
+    #[cfg(not(feature = "parallel"))]
+    let seeds = seeds.into_iter().flatten();
 
-    pub fn src_dst(&self, source: usize) -> Option<usize> {
-        if !self.contains_src(source) {
-            return None;
-        }
+    #[cfg(feature = "progress")]
+    let seeds = seeds.progress_count(count);
 
-        let offset = source - self.src.start;
-        Some(self.dest.start + offset)
-    }
+    seeds.map(|seed| almanac.location(seed)).min()
 }
 
-pub fn process(input: &str) -> Result<usize> {
-    let seeds = parse_seeds(input)?;
-    let map = parse_maps(input);
-    let count: usize = seeds.iter().map(|range| range.end - range.start).sum();
-
-    let lowest = seeds
-        .into_par_iter()
-        .progress_count(count as u64)
-        .flat_map(|range| range)
-        .map(|seed| map.iter().fold(seed, |seed, map| map.transpose(seed)))
-        .min()
-        .expect("Something went wrong, must have a lowest location");
-
-    Ok(lowest)
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::almanac::Map;
+    use crate::almanac::EXAMPLE;
+    use crate::common::XorShift;
+
+    #[test]
+    fn test_process() {
+        assert_eq!(process(EXAMPLE).unwrap(), 46);
+        assert_eq!(process_brute_force(EXAMPLE).unwrap(), 46);
+    }
 
-fn parse_seeds(input: &str) -> Result<Vec<std::ops::Range<usize>>> {
-    let mut lines = input.lines();
-    let Some(line) = lines.next() else {
-        return Err(anyhow!("Invalid input"));
-    };
+    #[test]
+    fn test_process_strict() {
+        assert_eq!(process_strict(EXAMPLE).unwrap(), 46);
 
-    let (_, numbers) = line
-        .split_once(':')
-        .ok_or(anyhow!("invalid seeds inputs"))?;
+        let input = "seeds: 5 2\n\nseed-to-location map:\n1 0 10\n2 5 10";
+        assert!(process_strict(input).is_err());
+    }
 
-    let seeds = numbers
-        .trim()
-        .split_ascii_whitespace()
-        .filter_map(|s| s.parse::<usize>().ok())
-        .collect::<Vec<usize>>();
+    #[test]
+    fn test_no_seeds() {
+        let input = "seeds:\n\nseed-to-soil map:\n50 98 2";
+        assert!(process(input).is_err());
+        assert!(process_brute_force(input).is_err());
+    }
 
-    let seeds = seeds
-        .iter()
-        .cloned()
-        .step_by(2)
-        .zip(seeds.iter().skip(1).step_by(2))
-        .map(|(start, offset)| (start..start + offset))
-        .collect::<Vec<std::ops::Range<usize>>>();
+    #[test]
+    fn test_process_input() {
+        let input = std::fs::read_to_string("input1.txt").unwrap();
+        assert_eq!(process(&input).unwrap(), 5200543);
+    }
 
-    Ok(seeds)
-}
+    // Builds small random almanacs and checks the interval path against the
+    // brute force one.
+    #[test]
+    fn test_process_against_brute_force() {
+        let mut random = XorShift::default();
+        let mut next = |max: usize| random.below(max);
+
+        let headers = [
+            "seed-to-soil",
+            "soil-to-fertilizer",
+            "fertilizer-to-water",
+            "water-to-light",
+            "light-to-temperature",
+            "temperature-to-humidity",
+            "humidity-to-location",
+        ];
+
+        for _ in 0..200 {
+            let seeds = (0..next(3) + 1)
+                .map(|_| format!("{} {}", next(100), next(30) + 1))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let mut input = format!("seeds: {}\n", seeds);
+            for header in headers {
+                input.push_str(&format!("\n{} map:\n", header));
+                for _ in 0..next(4) + 1 {
+                    input.push_str(&format!("{} {} {}\n", next(120), next(120), next(25) + 1));
+                }
+            }
 
-fn parse_maps(input: &str) -> Vec<Map> {
-    let mut maps: HashMap<u8, Vec<Range>> = HashMap::new();
-
-    let mut current_map: Option<u8> = None;
-    let mut vec_map: Vec<Map> = Vec::with_capacity(MapKey::HumidityToLocation as usize + 1);
-
-    for line in input.lines() {
-        if line.ends_with("map:") {
-            current_map = match line {
-                "seed-to-soil map:" => Some(MapKey::SeedToSoil as u8),
-                "soil-to-fertilizer map:" => Some(MapKey::SoilToFertilizer as u8),
-                "fertilizer-to-water map:" => Some(MapKey::FertilizerToWater as u8),
-                "water-to-light map:" => Some(MapKey::WaterToLight as u8),
-                "light-to-temperature map:" => Some(MapKey::LightToTemperature as u8),
-                "temperature-to-humidity map:" => Some(MapKey::TemperatureToHumidity as u8),
-                "humidity-to-location map:" => Some(MapKey::HumidityToLocation as u8),
-                _ => None,
-            };
-        } else if let Some(map_key) = &current_map {
-            if !line.is_empty() && !line.starts_with("seeds:") {
-                let values: Vec<usize> = line
-                    .split_whitespace()
-                    .map(|s| s.parse::<usize>().unwrap())
-                    .collect();
-                maps.entry(*map_key)
-                    .or_insert_with(Vec::new)
-                    .push((values[0], values[1], values[2]).into());
+            assert_eq!(
+                process(&input).unwrap(),
+                process_brute_force(&input).unwrap(),
+                "{}",
+                input
+            );
+
+            let almanac = Almanac::parse(&input).unwrap();
+            let composed = Map::compose_all(&almanac.maps);
+            for seed in 0..150 {
+                assert_eq!(
+                    composed.transpose(seed),
+                    almanac.location(seed),
+                    "{}",
+                    input
+                );
             }
         }
     }
-
-    for key in 0..=MapKey::HumidityToLocation as u8 {
-        let m = Map::new(maps.get(&key).expect("map_empty").to_vec());
-        vec_map.push(m);
-    }
-
-    println!("maps: {}", vec_map.len());
-    vec_map
-        .iter()
-        .enumerate()
-        .for_each(|(i, map)| println!("map{}: {} ranges", i, map.len()));
-
-    vec_map
 }
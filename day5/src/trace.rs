@@ -0,0 +1,218 @@
+use std::fmt;
+
+use crate::almanac::Almanac;
+
+// What a map did with a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Hit {
+    // the range line as written in the input, index is its position in the map
+    Range {
+        index: usize,
+        dest: usize,
+        src: usize,
+        len: usize,
+    },
+    Identity,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub from: String,
+    pub to: String,
+    pub input: usize,
+    pub output: usize,
+    pub hit: Hit,
+}
+
+// Every intermediate value a seed goes through until its location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    pub seed: usize,
+    pub steps: Vec<Step>,
+}
+
+impl Trace {
+    pub fn location(&self) -> usize {
+        self.steps.last().map_or(self.seed, |step| step.output)
+    }
+}
+
+impl Almanac {
+    pub fn trace(&self, seed: usize) -> Trace {
+        let mut value = seed;
+        let steps = self
+            .maps
+            .iter()
+            .zip(self.categories.windows(2))
+            .map(|(map, categories)| {
+                let (output, hit) = match map.find(value) {
+                    Some((_, range)) => {
+                        let written = range.written();
+                        (
+                            range.src_dst(value).unwrap_or(value),
+                            Hit::Range {
+                                index: written.index,
+                                dest: written.dest,
+                                src: written.src,
+                                len: written.len,
+                            },
+                        )
+                    }
+                    None => (value, Hit::Identity),
+                };
+
+                let step = Step {
+                    from: categories[0].clone(),
+                    to: categories[1].clone(),
+                    input: value,
+                    output,
+                    hit,
+                };
+                value = output;
+                step
+            })
+            .collect();
+
+        Trace { seed, steps }
+    }
+}
+
+impl fmt::Display for Hit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            // bounds as written, they might not fit in a usize
+            Hit::Range {
+                index,
+                dest,
+                src,
+                len,
+            } => write!(
+                f,
+                "range {}: {}..{} -> {}..{}",
+                index,
+                src,
+                *src as u128 + *len as u128,
+                dest,
+                *dest as u128 + *len as u128
+            ),
+            Hit::Identity => write!(f, "identity"),
+        }
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = |values: &mut dyn Iterator<Item = usize>, header: &str| {
+            values.max().unwrap_or(0).max(header.len())
+        };
+        let from_width = width(&mut self.steps.iter().map(|step| step.from.len()), "from");
+        let to_width = width(&mut self.steps.iter().map(|step| step.to.len()), "to");
+        let input_width = width(
+            &mut self.steps.iter().map(|step| step.input.to_string().len()),
+            "input",
+        );
+        let output_width = width(
+            &mut self.steps.iter().map(|step| step.output.to_string().len()),
+            "output",
+        );
+
+        writeln!(f, "seed {} -> location {}", self.seed, self.location())?;
+        writeln!(
+            f,
+            "{:<from_width$}  {:<to_width$}  {:>input_width$}  {:>output_width$}  hit",
+            "from", "to", "input", "output"
+        )?;
+        for step in self.steps.iter() {
+            writeln!(
+                f,
+                "{:<from_width$}  {:<to_width$}  {:>input_width$}  {:>output_width$}  {}",
+                step.from, step.to, step.input, step.output, step.hit
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::almanac::EXAMPLE;
+
+    #[test]
+    fn test_trace() {
+        let almanac = Almanac::parse(EXAMPLE).unwrap();
+        let trace = almanac.trace(79);
+
+        assert_eq!(trace.location(), 82);
+        assert_eq!(trace.steps.len(), 7);
+        assert_eq!(
+            trace.steps[..3],
+            [
+                Step {
+                    from: "seed".to_string(),
+                    to: "soil".to_string(),
+                    input: 79,
+                    output: 81,
+                    hit: Hit::Range {
+                        index: 1,
+                        dest: 52,
+                        src: 50,
+                        len: 48
+                    },
+                },
+                Step {
+                    from: "soil".to_string(),
+                    to: "fertilizer".to_string(),
+                    input: 81,
+                    output: 81,
+                    hit: Hit::Identity,
+                },
+                Step {
+                    from: "fertilizer".to_string(),
+                    to: "water".to_string(),
+                    input: 81,
+                    output: 81,
+                    hit: Hit::Identity,
+                },
+            ]
+        );
+
+        let table = trace.to_string();
+        assert!(table.starts_with("seed 79 -> location 82\n"));
+        assert_eq!(table.lines().count(), 9);
+        assert!(table.contains("range 1: 50..98 -> 52..100"));
+    }
+
+    #[test]
+    fn test_trace_unsorted_map() {
+        // the third range overlaps the second, Map::new keeps only 20..30 of it
+        let almanac = Almanac::parse(
+            "seeds: 25
+
+seed-to-location map:
+10 100 5
+50 0 20
+0 10 20",
+        )
+        .unwrap();
+
+        let trace = almanac.trace(25);
+        assert_eq!(trace.location(), 15);
+        assert_eq!(
+            trace.steps[0].hit,
+            Hit::Range {
+                index: 2,
+                dest: 0,
+                src: 10,
+                len: 20
+            }
+        );
+        assert_eq!(trace.steps[0].hit.to_string(), "range 2: 10..30 -> 0..20");
+
+        let trace = almanac.trace(101);
+        assert_eq!(
+            trace.steps[0].hit.to_string(),
+            "range 0: 100..105 -> 10..15"
+        );
+    }
+}
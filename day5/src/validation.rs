@@ -0,0 +1,236 @@
+use std::fmt;
+
+use anyhow::{anyhow, Result};
+
+use crate::category::parse_blocks;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    // two source ranges of the same map overlap, transpose picks the first one
+    Overlap {
+        map: String,
+        first: usize,
+        second: usize,
+    },
+    // a range with length zero never matches anything
+    Empty {
+        map: String,
+        index: usize,
+    },
+    // src + len or dest + len does not fit in a usize
+    Overflow {
+        map: String,
+        index: usize,
+    },
+    // values between two ranges fall back to the identity
+    Gap {
+        map: String,
+        start: usize,
+        end: usize,
+    },
+}
+
+impl Issue {
+    // Gaps are legit, the identity fallback is part of the puzzle,
+    // anything else makes the almanac ambiguous.
+    pub fn is_ambiguous(&self) -> bool {
+        !matches!(self, Issue::Gap { .. })
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::Overlap { map, first, second } => write!(
+                f,
+                "{}: ranges {} and {} have overlapping sources",
+                map, first, second
+            ),
+            Issue::Empty { map, index } => write!(f, "{}: range {} is empty", map, index),
+            Issue::Overflow { map, index } => write!(f, "{}: range {} overflows", map, index),
+            Issue::Gap { map, start, end } => {
+                write!(f, "{}: {}..{} is not covered by any range", map, start, end)
+            }
+        }
+    }
+}
+
+// Reports every issue found in the maps of the almanac, ranges are identified
+// by their position inside the map as written in the input.
+pub fn validate(input: &str) -> Result<Vec<Issue>> {
+    let mut issues = Vec::new();
+    for block in parse_blocks(input)? {
+        let map = format!("{}-to-{}", block.from, block.to);
+        validate_map(&map, &block.ranges, &mut issues);
+    }
+
+    Ok(issues)
+}
+
+// Strict mode, refuses almanacs with ambiguous maps.
+pub fn check(input: &str) -> Result<()> {
+    let ambiguous = validate(input)?
+        .into_iter()
+        .filter(Issue::is_ambiguous)
+        .map(|issue| issue.to_string())
+        .collect::<Vec<String>>();
+
+    if !ambiguous.is_empty() {
+        return Err(anyhow!("Ambiguous almanac:\n{}", ambiguous.join("\n")));
+    }
+    Ok(())
+}
+
+fn validate_map(map: &str, ranges: &[(usize, usize, usize)], issues: &mut Vec<Issue>) {
+    let mut sources = Vec::with_capacity(ranges.len());
+
+    for (index, &(dest, src, len)) in ranges.iter().enumerate() {
+        if len == 0 {
+            issues.push(Issue::Empty {
+                map: map.to_string(),
+                index,
+            });
+            continue;
+        }
+
+        match (src.checked_add(len), dest.checked_add(len)) {
+            (Some(end), Some(_)) => sources.push((src, end, index)),
+            _ => issues.push(Issue::Overflow {
+                map: map.to_string(),
+                index,
+            }),
+        }
+    }
+
+    sources.sort();
+
+    // sorted by start, every later range starting before this one ends
+    // overlaps it, anything below the furthest end seen so far is covered
+    let mut covered = 0;
+    for (i, &(start, end, index)) in sources.iter().enumerate() {
+        if start > covered {
+            issues.push(Issue::Gap {
+                map: map.to_string(),
+                start: covered,
+                end: start,
+            });
+        }
+        covered = covered.max(end);
+
+        for &(_, _, other) in sources[i + 1..]
+            .iter()
+            .take_while(|(other_start, _, _)| *other_start < end)
+        {
+            issues.push(Issue::Overlap {
+                map: map.to_string(),
+                first: index.min(other),
+                second: index.max(other),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let input = "seeds: 1 2
+
+seed-to-soil map:
+50 98 2
+52 50 48
+0 10 5
+
+soil-to-fertilizer map:
+0 15 37
+37 20 2
+39 0 0
+1 18446744073709551610 10";
+
+        let issues = validate(input).unwrap();
+        assert_eq!(
+            issues,
+            vec![
+                Issue::Gap {
+                    map: "seed-to-soil".to_string(),
+                    start: 0,
+                    end: 10
+                },
+                Issue::Gap {
+                    map: "seed-to-soil".to_string(),
+                    start: 15,
+                    end: 50
+                },
+                Issue::Empty {
+                    map: "soil-to-fertilizer".to_string(),
+                    index: 2
+                },
+                Issue::Overflow {
+                    map: "soil-to-fertilizer".to_string(),
+                    index: 3
+                },
+                Issue::Gap {
+                    map: "soil-to-fertilizer".to_string(),
+                    start: 0,
+                    end: 15
+                },
+                Issue::Overlap {
+                    map: "soil-to-fertilizer".to_string(),
+                    first: 0,
+                    second: 1
+                },
+            ]
+        );
+        assert!(check(input).is_err());
+    }
+
+    #[test]
+    fn test_check_allows_gaps() {
+        let input = "seed-to-soil map:
+50 98 2
+0 10 5";
+        assert_eq!(validate(input).unwrap().len(), 2);
+        assert!(check(input).is_ok());
+    }
+
+    #[test]
+    fn test_every_overlapping_pair() {
+        let input = "a-to-b map:
+0 0 100
+0 10 10
+0 30 10
+0 35 15";
+        let pairs = validate(input)
+            .unwrap()
+            .into_iter()
+            .filter_map(|issue| match issue {
+                Issue::Overlap { first, second, .. } => Some((first, second)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(pairs, vec![(0, 1), (0, 2), (0, 3), (2, 3)]);
+    }
+
+    #[test]
+    fn test_gap_before_first_range() {
+        assert_eq!(
+            validate("a-to-b map:\n5 10 5\n0 20 5").unwrap(),
+            vec![
+                Issue::Gap {
+                    map: "a-to-b".to_string(),
+                    start: 0,
+                    end: 10
+                },
+                Issue::Gap {
+                    map: "a-to-b".to_string(),
+                    start: 15,
+                    end: 20
+                },
+            ]
+        );
+        // a map starting at 0 has nothing before it
+        assert_eq!(validate("a-to-b map:\n5 0 5").unwrap(), vec![]);
+    }
+}
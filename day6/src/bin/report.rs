@@ -0,0 +1,20 @@
+use day6::boat::BoatModel;
+use day6::report::{report_with_model, to_json, to_table};
+
+// report [--json] [--charge-rate N] [--max-speed N] [--drag N]
+fn main() -> anyhow::Result<()> {
+    let input = std::fs::read_to_string("input1.txt")?;
+    let mut args = std::env::args().skip(1).collect::<Vec<String>>();
+    let json = args.iter().any(|arg| arg == "--json");
+    args.retain(|arg| arg != "--json");
+    let model = BoatModel::from_args(&args)?;
+
+    let reports = report_with_model(&input, &model)?;
+    if json {
+        println!("{}", to_json(&reports));
+    } else {
+        print!("{}", to_table(&reports));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,387 @@
+use anyhow::{anyhow, Result};
+
+use crate::solver::winning_interval;
+
+// How the boat behaves: every ms holding the button adds `charge_rate` mm/ms
+// of speed, up to `max_speed` if any. Once released the boat loses `drag` mm/ms
+// of speed every ms until it stops or the race ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoatModel {
+    pub charge_rate: u128,
+    pub max_speed: Option<u128>,
+    pub drag: u128,
+}
+
+// The puzzle rules, one mm/ms per ms held, no limit and no drag.
+impl Default for BoatModel {
+    fn default() -> Self {
+        Self {
+            charge_rate: 1,
+            max_speed: None,
+            drag: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RaceOutcome {
+    // first and last hold times beating the record
+    pub winning: Option<(u128, u128)>,
+    // number of hold times beating the record
+    pub options: u128,
+    // shortest hold time reaching the best distance
+    pub optimal_hold: u128,
+    pub best_distance: u128,
+    // how far the best distance goes past the record
+    pub margin: Option<u128>,
+}
+
+impl BoatModel {
+    pub fn speed(&self, hold: u128) -> Result<u128> {
+        let speed = self
+            .charge_rate
+            .checked_mul(hold)
+            .ok_or(anyhow!("Speed overflows holding {} ms", hold))?;
+        Ok(self.max_speed.map_or(speed, |max| speed.min(max)))
+    }
+
+    pub fn distance(&self, hold: u128, race_time: u128) -> Result<u128> {
+        if hold > race_time {
+            return Ok(0);
+        }
+        let speed = self.speed(hold)?;
+        let moving = race_time - hold;
+
+        // ms the boat keeps moving, speed - drag * k > 0
+        let steps = match self.drag {
+            0 => moving,
+            drag => moving.min(speed.div_ceil(drag)),
+        };
+
+        let overflow = || anyhow!("Distance overflows holding {} ms", hold);
+        let travelled = steps.checked_mul(speed).ok_or_else(overflow)?;
+        let lost = steps
+            .checked_mul(steps.saturating_sub(1))
+            .and_then(|v| (v / 2).checked_mul(self.drag))
+            .ok_or_else(overflow)?;
+        Ok(travelled - lost)
+    }
+
+    pub fn solve(&self, race_time: u128, record: u128) -> Result<RaceOutcome> {
+        if *self == Self::default() {
+            return self.solve_default(race_time, record);
+        }
+
+        // Distance strictly grows with the hold time until the peak, stays there
+        // while the boat still stops before the race ends, then strictly drops,
+        // so the peak and both win boundaries are found by binary search.
+        let distance = |hold: u128| self.distance(hold, race_time);
+
+        // first hold time not improving on the next one
+        let optimal_hold = partition_point(0, race_time, |hold| {
+            Ok(distance(hold + 1)? > distance(hold)?)
+        })?;
+        let best_distance = distance(optimal_hold)?;
+
+        let winning = if best_distance > record {
+            let first = partition_point(0, optimal_hold, |hold| Ok(distance(hold)? <= record))?;
+            // holding for the whole race never moves the boat, so the search
+            // stops before race_time and never has to step past u128::MAX
+            let last = partition_point(optimal_hold, race_time, |hold| {
+                Ok(distance(hold)? > record)
+            })? - 1;
+            Some((first, last))
+        } else {
+            None
+        };
+
+        Ok(RaceOutcome {
+            winning,
+            // every hold between the boundaries wins, the distance never dips
+            // below its ends on the way to the peak
+            options: winning.map_or(0, |(first, last)| last - first + 1),
+            optimal_hold,
+            best_distance,
+            margin: best_distance.checked_sub(record).filter(|m| *m > 0),
+        })
+    }
+
+    // Reads `--charge-rate N`, `--max-speed N` and `--drag N`, missing ones
+    // keep the puzzle rules. Any other argument is an error.
+    pub fn from_args(args: &[String]) -> Result<Self> {
+        let mut model = Self::default();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = || -> Result<u128> {
+                let value = args.next().ok_or(anyhow!("Missing value for {}", arg))?;
+                value
+                    .parse::<u128>()
+                    .map_err(|_| anyhow!("Invalid value for {}: {}", arg, value))
+            };
+            match arg.as_str() {
+                "--charge-rate" => model.charge_rate = value()?,
+                "--max-speed" => model.max_speed = Some(value()?),
+                "--drag" => model.drag = value()?,
+                _ => return Err(anyhow!("Unknown argument: {}", arg)),
+            }
+        }
+
+        Ok(model)
+    }
+
+    // closed form for the puzzle rules
+    fn solve_default(&self, race_time: u128, record: u128) -> Result<RaceOutcome> {
+        let winning = winning_interval(race_time, record)?;
+        let optimal_hold = race_time / 2;
+        let best_distance = self.distance(optimal_hold, race_time)?;
+
+        Ok(RaceOutcome {
+            winning,
+            options: winning.map_or(0, |(first, last)| last - first + 1),
+            optimal_hold,
+            best_distance,
+            margin: best_distance.checked_sub(record).filter(|m| *m > 0),
+        })
+    }
+}
+
+// First value in start..end where `pred` turns false, `pred` must hold for a
+// prefix of the range only. Returns end when it never turns false.
+fn partition_point(
+    mut start: u128,
+    mut end: u128,
+    pred: impl Fn(u128) -> Result<bool>,
+) -> Result<u128> {
+    while start < end {
+        let mid = start + (end - start) / 2;
+        if pred(mid)? {
+            start = mid + 1;
+        } else {
+            end = mid;
+        }
+    }
+    Ok(start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::count_options;
+
+    // Moves the boat one ms at a time.
+    fn simulate(model: &BoatModel, hold: u128, race_time: u128) -> u128 {
+        let mut speed = model.speed(hold).unwrap();
+        let mut distance = 0;
+        for _ in hold..race_time {
+            distance += speed;
+            speed = speed.saturating_sub(model.drag);
+        }
+        distance
+    }
+
+    #[test]
+    fn test_default_model() {
+        let model = BoatModel::default();
+        for race_time in 0..=40 {
+            for record in 0..=race_time * race_time / 4 + 1 {
+                let outcome = model.solve(race_time, record).unwrap();
+                assert_eq!(outcome.options, count_options(race_time, record).unwrap());
+            }
+        }
+
+        let outcome = model.solve(30, 200).unwrap();
+        assert_eq!(outcome.winning, Some((11, 19)));
+        assert_eq!(outcome.optimal_hold, 15);
+        assert_eq!(outcome.best_distance, 225);
+        assert_eq!(outcome.margin, Some(25));
+    }
+
+    #[test]
+    fn test_distance_against_simulation() {
+        let models = [
+            BoatModel {
+                charge_rate: 2,
+                max_speed: None,
+                drag: 0,
+            },
+            BoatModel {
+                charge_rate: 1,
+                max_speed: Some(5),
+                drag: 0,
+            },
+            BoatModel {
+                charge_rate: 3,
+                max_speed: Some(20),
+                drag: 2,
+            },
+            BoatModel {
+                charge_rate: 1,
+                max_speed: None,
+                drag: 1,
+            },
+        ];
+
+        for model in models.iter() {
+            for race_time in 0..40 {
+                for hold in 0..=race_time {
+                    assert_eq!(
+                        model.distance(hold, race_time).unwrap(),
+                        simulate(model, hold, race_time),
+                        "{:?} hold {} race {}",
+                        model,
+                        hold,
+                        race_time
+                    );
+                }
+            }
+        }
+    }
+
+    // The linear scan solve used to run, every hold time one by one.
+    fn solve_linear(model: &BoatModel, race_time: u128, record: u128) -> RaceOutcome {
+        let mut outcome = RaceOutcome {
+            winning: None,
+            options: 0,
+            optimal_hold: 0,
+            best_distance: 0,
+            margin: None,
+        };
+        for hold in 0..=race_time {
+            let distance = model.distance(hold, race_time).unwrap();
+            if distance > outcome.best_distance {
+                outcome.best_distance = distance;
+                outcome.optimal_hold = hold;
+            }
+            if distance > record {
+                outcome.options += 1;
+                outcome.winning = match outcome.winning {
+                    Some((first, _)) => Some((first, hold)),
+                    None => Some((hold, hold)),
+                };
+            }
+        }
+        outcome.margin = outcome.best_distance.checked_sub(record).filter(|m| *m > 0);
+        outcome
+    }
+
+    #[test]
+    fn test_solve_against_linear() {
+        for charge_rate in 0..=3 {
+            for max_speed in [None, Some(1), Some(4), Some(9)] {
+                for drag in 0..=4 {
+                    let model = BoatModel {
+                        charge_rate,
+                        max_speed,
+                        drag,
+                    };
+                    for race_time in 0..=30 {
+                        let best = solve_linear(&model, race_time, 0).best_distance;
+                        for record in 0..=best + 1 {
+                            assert_eq!(
+                                model.solve(race_time, record).unwrap(),
+                                solve_linear(&model, race_time, record),
+                                "{:?} race {} record {}",
+                                model,
+                                race_time,
+                                record
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_long_races() {
+        let model = BoatModel {
+            charge_rate: 2,
+            max_speed: Some(1_000_000),
+            drag: 3,
+        };
+        // the boat stops after 333_334 ms, any hold leaving that much time reaches the peak
+        let outcome = model.solve(300_000_000, 1_000).unwrap();
+        assert_eq!(outcome.optimal_hold, 500_000);
+        assert_eq!(
+            outcome.best_distance,
+            model.distance(500_000, 300_000_000).unwrap()
+        );
+        let (first, last) = outcome.winning.unwrap();
+        assert!(model.distance(first, 300_000_000).unwrap() > 1_000);
+        assert!(model.distance(first - 1, 300_000_000).unwrap() <= 1_000);
+        assert!(model.distance(last, 300_000_000).unwrap() > 1_000);
+        assert!(model.distance(last + 1, 300_000_000).unwrap() <= 1_000);
+
+        let model = BoatModel {
+            charge_rate: 1,
+            max_speed: Some(1 << 20),
+            drag: 0,
+        };
+        let outcome = model.solve(1 << 60, 1 << 81).unwrap();
+        assert_eq!(outcome.optimal_hold, 1 << 20);
+        assert_eq!(outcome.winning, None);
+    }
+
+    #[test]
+    fn test_longest_race() {
+        let model = BoatModel {
+            charge_rate: 1,
+            max_speed: Some(10),
+            drag: 10,
+        };
+        let outcome = model.solve(u128::MAX, 0).unwrap();
+        assert_eq!(outcome.winning, Some((1, u128::MAX - 1)));
+        assert_eq!(outcome.options, u128::MAX - 1);
+        assert_eq!(outcome.optimal_hold, 10);
+        assert_eq!(outcome.best_distance, 10);
+    }
+
+    #[test]
+    fn test_from_args() {
+        let args = |line: &str| {
+            line.split_whitespace()
+                .map(String::from)
+                .collect::<Vec<String>>()
+        };
+
+        assert_eq!(
+            BoatModel::from_args(&args("")).unwrap(),
+            BoatModel::default()
+        );
+        assert_eq!(
+            BoatModel::from_args(&args("--drag 2 --max-speed 5 --charge-rate 3")).unwrap(),
+            BoatModel {
+                charge_rate: 3,
+                max_speed: Some(5),
+                drag: 2
+            }
+        );
+        assert!(BoatModel::from_args(&args("--drag")).is_err());
+        assert!(BoatModel::from_args(&args("--drag x")).is_err());
+        assert!(BoatModel::from_args(&args("--json")).is_err());
+        assert!(BoatModel::from_args(&args("--max_speed 5")).is_err());
+        assert!(BoatModel::from_args(&args("--dragg 2")).is_err());
+    }
+
+    #[test]
+    fn test_custom_model() {
+        // speed caps at 5 so holding longer than 5 ms only wastes time
+        let model = BoatModel {
+            charge_rate: 1,
+            max_speed: Some(5),
+            drag: 0,
+        };
+        let outcome = model.solve(10, 20).unwrap();
+        assert_eq!(outcome.winning, Some((3, 5)));
+        assert_eq!(outcome.options, 3);
+        assert_eq!(outcome.optimal_hold, 5);
+        assert_eq!(outcome.best_distance, 25);
+        assert_eq!(outcome.margin, Some(5));
+
+        let outcome = model.solve(10, 25).unwrap();
+        assert_eq!(outcome.winning, None);
+        assert_eq!(outcome.options, 0);
+        assert_eq!(outcome.margin, None);
+    }
+}
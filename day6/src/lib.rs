@@ -1,3 +1,6 @@
+pub mod boat;
 pub mod part1;
 pub mod part2;
-
+pub mod report;
+pub mod sheet;
+pub mod solver;
@@ -1,63 +1,50 @@
 use anyhow::{anyhow, Result};
 
-pub fn process(input: &str) -> Result<usize> {
-    let mut lines = input.lines();
-    let times = parse_time(lines.next().ok_or(anyhow!("Input empty"))?)?;
-
-    let distances = parse_distance(lines.next().ok_or(anyhow!("Input empty"))?)?;
-
-    if times.len() != distances.len() {
-        return Err(anyhow!("Invalid race input"));
-    }
-
-    let options =
-        times
-            .into_iter()
-            .zip(distances.into_iter())
-            .fold(1, |mut options, (time, distance)| {
-                options *= get_options(time, distance).count();
-                options
-            });
+use crate::boat::BoatModel;
+use crate::sheet::RaceSheet;
 
-    Ok(options)
-}
-
-fn parse_time(line: &str) -> Result<Vec<u32>> {
-    parse_values(line)
+pub fn process(input: &str) -> Result<usize> {
+    process_with_model(input, &BoatModel::default())
 }
 
-fn parse_distance(line: &str) -> Result<Vec<u32>> {
-    parse_values(line)
+// Same as process but counting the winning options of a custom boat.
+pub fn process_with_model(input: &str, model: &BoatModel) -> Result<usize> {
+    let races = RaceSheet::parse(input)?.as_races()?;
+    let options = races
+        .into_iter()
+        .try_fold(1u128, |options, (time, distance)| {
+            options
+                .checked_mul(model.solve(time, distance)?.options)
+                .ok_or(anyhow!("Number of options overflows"))
+        })?;
+
+    Ok(usize::try_from(options)?)
 }
 
-fn parse_values(line: &str) -> Result<Vec<u32>> {
-    let (_, numbers) = line.split_once(':').ok_or(anyhow!("Race time inputs"))?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let values = numbers
-        .trim()
-        .split_ascii_whitespace()
-        .filter_map(|s| s.parse::<u32>().ok())
-        .collect::<Vec<u32>>();
+    const INPUT: &str = "Time:      7  15   30
+Distance:  9  40  200";
 
-    if values.is_empty() {
-        return Err(anyhow!("Empty race inputs"));
+    #[test]
+    fn test_process() {
+        assert_eq!(process(INPUT).unwrap(), 288);
     }
 
-    Ok(values)
-}
-
-// get race time, record distance and
-// returns the options we can hold the buttom at the start to win
-fn get_options(race_time: u32, record: u32) -> impl Iterator<Item = u32> {
-    (0..=race_time).filter(move |time| {
-        if *time == race_time {
-            return false;
-        }
-        let time_left = race_time - time;
-        let distance = time * time_left;
-        if distance > record {
-            return true;
-        }
-        false
-    })
+    #[test]
+    fn test_process_with_model() {
+        // speed caps at 5: the 7 ms race wins holding 2..=5, the 15 ms race
+        // holding 4..=6 and the 30 ms race never gets past 125
+        let model = BoatModel {
+            charge_rate: 1,
+            max_speed: Some(5),
+            drag: 0,
+        };
+        let input = "Time:      7  15
+Distance:  9  40";
+        assert_eq!(process_with_model(input, &model).unwrap(), 12);
+        assert_eq!(process_with_model(INPUT, &model).unwrap(), 0);
+    }
 }
@@ -1,50 +1,43 @@
-use anyhow::{anyhow, Result};
+use anyhow::Result;
+
+use crate::sheet::RaceSheet;
+use crate::solver::count_options;
 
 // One millisecond holded -> One milimiter advanced
 
 pub fn process(input: &str) -> Result<usize> {
-    let mut lines = input.lines();
-    let time = parse_time(lines.next().ok_or(anyhow!("Input empty"))?)?;
-
-    let distance = parse_distance(lines.next().ok_or(anyhow!("Input empty"))?)?;
-
-    let options = get_options(time, distance).count();
-
-    Ok(options)
-}
+    let (time, distance) = RaceSheet::parse(input)?.as_kerned_race()?;
 
-fn parse_time(line: &str) -> Result<usize> {
-    parse_value(line)
-}
+    let options = count_options(time, distance)?;
 
-fn parse_distance(line: &str) -> Result<usize> {
-    parse_value(line)
+    Ok(usize::try_from(options)?)
 }
 
-fn parse_value(line: &str) -> Result<usize> {
-    let (_, numbers) = line.split_once(':').ok_or(anyhow!("Race time inputs"))?;
-
-    let values = numbers.trim().split_ascii_whitespace().collect::<String>();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if values.is_empty() {
-        return Err(anyhow!("Empty race inputs"));
+    #[test]
+    fn test_process() {
+        let input = "Time:      7  15   30
+Distance:  9  40  200";
+        assert_eq!(process(input).unwrap(), 71503);
     }
 
-    Ok(values.parse::<usize>()?)
-}
-
-// get race time, record distance and
-// returns the options we can hold the buttom at the start to win
-fn get_options(race_time: usize, record: usize) -> impl Iterator<Item = usize> {
-    (0..=race_time).filter(move |time| {
-        if *time == race_time {
-            return false;
-        }
-        let time_left = race_time - time;
-        let distance = time * time_left;
-        if distance > record {
-            return true;
-        }
-        false
-    })
+    #[test]
+    fn test_long_values() {
+        // kerned into 10^19, beyond what the old usize product could hold
+        let input = "Time:      1000000000 0000000000
+Distance:  0";
+        assert_eq!(process(input).unwrap(), 9_999_999_999_999_999_999);
+
+        // 2^64, its square does not fit a u128
+        let input = "Time:      1844674407 3709551616
+Distance:  1";
+        assert!(process(input).is_err());
+
+        let input = "Time:      7  1x5   30
+Distance:  9  40  200";
+        assert!(process(input).is_err());
+    }
 }
@@ -0,0 +1,167 @@
+use anyhow::Result;
+
+use crate::boat::BoatModel;
+use crate::sheet::RaceSheet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RaceReport {
+    pub time: u128,
+    pub record: u128,
+    pub first_winning: Option<u128>,
+    pub last_winning: Option<u128>,
+    pub options: u128,
+    pub optimal_hold: u128,
+    pub best_distance: u128,
+    pub margin: Option<u128>,
+}
+
+pub fn report(input: &str) -> Result<Vec<RaceReport>> {
+    report_with_model(input, &BoatModel::default())
+}
+
+pub fn report_with_model(input: &str, model: &BoatModel) -> Result<Vec<RaceReport>> {
+    RaceSheet::parse(input)?
+        .as_races()?
+        .into_iter()
+        .map(|(time, record)| {
+            let outcome = model.solve(time, record)?;
+            Ok(RaceReport {
+                time,
+                record,
+                first_winning: outcome.winning.map(|(first, _)| first),
+                last_winning: outcome.winning.map(|(_, last)| last),
+                options: outcome.options,
+                optimal_hold: outcome.optimal_hold,
+                best_distance: outcome.best_distance,
+                margin: outcome.margin,
+            })
+        })
+        .collect()
+}
+
+const HEADERS: [&str; 8] = [
+    "time", "record", "first", "last", "options", "optimal", "best", "margin",
+];
+
+impl RaceReport {
+    fn columns(&self) -> [String; 8] {
+        let optional = |value: Option<u128>| value.map_or("-".to_string(), |v| v.to_string());
+        [
+            self.time.to_string(),
+            self.record.to_string(),
+            optional(self.first_winning),
+            optional(self.last_winning),
+            self.options.to_string(),
+            self.optimal_hold.to_string(),
+            self.best_distance.to_string(),
+            optional(self.margin),
+        ]
+    }
+
+    pub fn to_json(&self) -> String {
+        let optional = |value: Option<u128>| value.map_or("null".to_string(), |v| v.to_string());
+        format!(
+            "{{\"time\":{},\"record\":{},\"first_winning\":{},\"last_winning\":{},\"options\":{},\"optimal_hold\":{},\"best_distance\":{},\"margin\":{}}}",
+            self.time,
+            self.record,
+            optional(self.first_winning),
+            optional(self.last_winning),
+            self.options,
+            self.optimal_hold,
+            self.best_distance,
+            optional(self.margin)
+        )
+    }
+}
+
+// Right aligned columns, one race per line.
+pub fn to_table(reports: &[RaceReport]) -> String {
+    let rows = reports.iter().map(RaceReport::columns).collect::<Vec<_>>();
+    let widths = (0..HEADERS.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].len())
+                .chain(std::iter::once(HEADERS[i].len()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<usize>>();
+
+    let line = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+            .collect::<Vec<String>>()
+            .join("  ")
+    };
+
+    std::iter::once(line(HEADERS.to_vec()))
+        .chain(
+            rows.iter()
+                .map(|row| line(row.iter().map(String::as_str).collect())),
+        )
+        .map(|line| line + "\n")
+        .collect()
+}
+
+pub fn to_json(reports: &[RaceReport]) -> String {
+    let races = reports
+        .iter()
+        .map(RaceReport::to_json)
+        .collect::<Vec<String>>();
+    format!("[{}]", races.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "Time:      7  15   30
+Distance:  9  40  200";
+
+    #[test]
+    fn test_report() {
+        let reports = report(INPUT).unwrap();
+        assert_eq!(reports.len(), 3);
+        assert_eq!(
+            reports[2],
+            RaceReport {
+                time: 30,
+                record: 200,
+                first_winning: Some(11),
+                last_winning: Some(19),
+                options: 9,
+                optimal_hold: 15,
+                best_distance: 225,
+                margin: Some(25),
+            }
+        );
+        assert_eq!(
+            reports
+                .iter()
+                .map(|report| report.options)
+                .product::<u128>(),
+            288
+        );
+    }
+
+    #[test]
+    fn test_formats() {
+        let reports = report("Time: 7 4\nDistance: 9 4").unwrap();
+
+        let table = to_table(&reports);
+        assert_eq!(
+            table,
+            "time  record  first  last  options  optimal  best  margin
+   7       9      2     5        4        3    12       3
+   4       4      -     -        0        2     4       -
+"
+        );
+
+        assert_eq!(
+            to_json(&reports[1..]),
+            "[{\"time\":4,\"record\":4,\"first_winning\":null,\"last_winning\":null,\"options\":0,\"optimal_hold\":2,\"best_distance\":4,\"margin\":null}]"
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use anyhow::{anyhow, Result};
+
+// A number as written in the sheet, line and column are 1 based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub value: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Token {
+    fn parse(&self) -> Result<u128> {
+        self.value.parse::<u128>().map_err(|_| {
+            anyhow!(
+                "line {}, column {}: {} exceeds the supported range",
+                self.line,
+                self.column,
+                self.value
+            )
+        })
+    }
+}
+
+// The raw race sheet, both puzzle parts read the same tokens differently.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaceSheet {
+    pub times: Vec<Token>,
+    pub distances: Vec<Token>,
+}
+
+impl RaceSheet {
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+
+        let times = match lines.next() {
+            Some((i, line)) => parse_line(i + 1, line, "Time:")?,
+            None => return Err(anyhow!("Input empty")),
+        };
+        let distances = match lines.next() {
+            Some((i, line)) => parse_line(i + 1, line, "Distance:")?,
+            None => return Err(anyhow!("Missing Distance: line")),
+        };
+        if let Some((i, _)) = lines.next() {
+            return Err(anyhow!(
+                "line {}: unexpected content after distances",
+                i + 1
+            ));
+        }
+
+        Ok(Self { times, distances })
+    }
+
+    // Part 1, every column is its own race.
+    pub fn as_races(&self) -> Result<Vec<(u128, u128)>> {
+        if self.times.len() != self.distances.len() {
+            return Err(anyhow!(
+                "Invalid race input, {} times but {} distances",
+                self.times.len(),
+                self.distances.len()
+            ));
+        }
+
+        self.times
+            .iter()
+            .zip(self.distances.iter())
+            .map(|(time, distance)| Ok((time.parse()?, distance.parse()?)))
+            .collect()
+    }
+
+    // Part 2, the spaces are bad kerning and each line is a single number.
+    pub fn as_kerned_race(&self) -> Result<(u128, u128)> {
+        Ok((kern(&self.times)?, kern(&self.distances)?))
+    }
+}
+
+fn kern(tokens: &[Token]) -> Result<u128> {
+    let value = tokens
+        .iter()
+        .map(|token| token.value.as_str())
+        .collect::<String>();
+
+    value
+        .parse::<u128>()
+        .map_err(|_| anyhow!("Race value {} exceeds the supported range", value))
+}
+
+fn parse_line(line_number: usize, line: &str, label: &str) -> Result<Vec<Token>> {
+    let offset = line.len() - line.trim_start().len();
+    let numbers = line.trim_start().strip_prefix(label).ok_or(anyhow!(
+        "line {}: expected {} label",
+        line_number,
+        label
+    ))?;
+    let offset = offset + label.len();
+
+    let mut tokens = Vec::new();
+    let mut start = None;
+    // trailing space closes the last token
+    for (i, c) in numbers
+        .char_indices()
+        .chain(std::iter::once((numbers.len(), ' ')))
+    {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some(i),
+            (true, Some(token_start)) => {
+                let value = &numbers[token_start..i];
+                let column = line[..offset + token_start].chars().count() + 1;
+                if !value.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(anyhow!(
+                        "line {}, column {}: invalid number {}",
+                        line_number,
+                        column,
+                        value
+                    ));
+                }
+                tokens.push(Token {
+                    value: value.to_string(),
+                    line: line_number,
+                    column,
+                });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    if tokens.is_empty() {
+        return Err(anyhow!("line {}: empty race inputs", line_number));
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "Time:      7  15   30
+Distance:  9  40  200";
+
+    #[test]
+    fn test_views() {
+        let sheet = RaceSheet::parse(INPUT).unwrap();
+        assert_eq!(
+            sheet.times[1],
+            Token {
+                value: "15".to_string(),
+                line: 1,
+                column: 15
+            }
+        );
+        assert_eq!(sheet.as_races().unwrap(), vec![(7, 9), (15, 40), (30, 200)]);
+        assert_eq!(sheet.as_kerned_race().unwrap(), (71530, 940200));
+    }
+
+    #[test]
+    fn test_invalid_sheets() {
+        let error = |input: &str| RaceSheet::parse(input).unwrap_err().to_string();
+
+        assert_eq!(
+            error("Time:      7  1x5   30\nDistance:  9  40  200"),
+            "line 1, column 15: invalid number 1x5"
+        );
+        assert_eq!(
+            error("Distance:  9  40  200\nTime:      7  15   30"),
+            "line 1: expected Time: label"
+        );
+        assert_eq!(error("Time:      7  15   30"), "Missing Distance: line");
+        assert_eq!(error("Time:\nDistance:  9"), "line 1: empty race inputs");
+        assert_eq!(error(""), "Input empty");
+
+        let sheet = RaceSheet::parse("Time: 7 15\nDistance: 9").unwrap();
+        assert!(sheet.as_races().is_err());
+    }
+}
@@ -0,0 +1,158 @@
+use anyhow::{anyhow, Result};
+
+// Holding the button for t ms in a race of T ms travels t * (T - t) mm,
+// we win when that beats the record D, so the winning hold times are the
+// integers strictly between the roots of t^2 - T*t + D = 0.
+//
+// Everything is computed on u128, race times must keep T^2 inside a u128
+// (T < 2^64), longer ones return an error instead of a wrong count.
+
+// Number of hold times beating the record.
+pub fn count_options(race_time: u128, record: u128) -> Result<u128> {
+    Ok(match winning_interval(race_time, record)? {
+        Some((first, last)) => last - first + 1,
+        None => 0,
+    })
+}
+
+// First and last winning hold times, None if the record can not be beaten.
+pub fn winning_interval(race_time: u128, record: u128) -> Result<Option<(u128, u128)>> {
+    let time = race_time;
+    let square = time
+        .checked_mul(time)
+        .ok_or(anyhow!("Race time {} exceeds the supported range", time))?;
+    // the best distance is T^2 / 4, which is below 2^126
+    let Some(four_records) = record.checked_mul(4) else {
+        return Ok(None);
+    };
+    let Some(discriminant) = square.checked_sub(four_records) else {
+        return Ok(None);
+    };
+
+    let distance = |hold: u128| hold * (time - hold);
+    let root = isqrt(discriminant);
+
+    // (T - sqrt(disc)) / 2 is at most one step away from the first winner
+    // once the square root is exact, walk to it
+    let mut first = (time - root.min(time)) / 2;
+    while first > 0 && distance(first - 1) > record {
+        first -= 1;
+    }
+    while first <= time / 2 && distance(first) <= record {
+        first += 1;
+    }
+    if first > time / 2 {
+        return Ok(None);
+    }
+
+    // distances are symmetric around T / 2
+    let last = time - first;
+    Ok(Some((first, last)))
+}
+
+// Largest r with r * r <= n, newton iterations on integers so there is no
+// floating point rounding around perfect squares.
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    let mut x = 1u128 << (128 - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The original approach, tries every hold time.
+    fn count_iterative(race_time: u128, record: u128) -> u128 {
+        (0..=race_time)
+            .filter(|time| time * (race_time - time) > record)
+            .count() as u128
+    }
+
+    #[test]
+    fn test_isqrt() {
+        for n in 0..100_000u128 {
+            let root = isqrt(n);
+            assert!(root * root <= n && (root + 1) * (root + 1) > n, "{}", n);
+        }
+        for root in [u32::MAX as u128, u64::MAX as u128, 3_037_000_499, 1 << 60] {
+            assert_eq!(isqrt(root * root), root);
+            assert_eq!(isqrt(root * root - 1), root - 1);
+            assert_eq!(isqrt(root * root + 1), root);
+        }
+    }
+
+    #[test]
+    fn test_against_iterative() {
+        for race_time in 0..=120 {
+            for record in 0..=race_time * race_time / 4 + 2 {
+                assert_eq!(
+                    count_options(race_time, record).unwrap(),
+                    count_iterative(race_time, record),
+                    "T={} D={}",
+                    race_time,
+                    record
+                );
+            }
+        }
+    }
+
+    // Every T and D up to a few thousand, the iterative count for all records
+    // of a race comes from a histogram of the distances of each hold time.
+    #[test]
+    fn test_exhaustive() {
+        const MAX_RECORD: u128 = 4_000;
+
+        for race_time in 0..=2_000u128 {
+            let mut beaten = vec![0u128; MAX_RECORD as usize + 2];
+            for hold in 0..=race_time {
+                let distance = (hold * (race_time - hold)).min(MAX_RECORD + 1);
+                beaten[distance as usize] += 1;
+            }
+            // number of hold times with distance > record
+            let mut above = 0;
+            for record in (0..=MAX_RECORD).rev() {
+                above += beaten[record as usize + 1];
+                assert_eq!(
+                    count_options(race_time, record).unwrap(),
+                    above,
+                    "T={} D={}",
+                    race_time,
+                    record
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(count_options(7, 9).unwrap(), 4);
+        assert_eq!(count_options(15, 40).unwrap(), 8);
+        assert_eq!(count_options(30, 200).unwrap(), 9);
+        assert_eq!(count_options(71530, 940200).unwrap(), 71503);
+        assert_eq!(winning_interval(30, 200).unwrap(), Some((11, 19)));
+        assert_eq!(winning_interval(4, 4).unwrap(), None);
+    }
+
+    #[test]
+    fn test_large_values() {
+        // T = 2^64 - 1 and D just below the best distance leave the two middle hold times
+        let time = u64::MAX as u128;
+        let best = (time / 2) * (time - time / 2);
+        assert_eq!(count_options(time, best - 1).unwrap(), 2);
+        assert_eq!(count_options(time, best).unwrap(), 0);
+        assert_eq!(count_options(time, u128::MAX).unwrap(), 0);
+        assert_eq!(count_options(time, 0).unwrap(), time - 1);
+
+        assert!(count_options(1 << 64, 0).is_err());
+    }
+}
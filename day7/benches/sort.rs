@@ -0,0 +1,56 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use day7::camel::{sort_hands, Card, CardValues, Hand};
+use day7::rules::JokersWild;
+
+mod common;
+
+use common::XorShift;
+
+fn hands(count: usize) -> Vec<(Hand, u32)> {
+    let mut random = XorShift::default();
+    (0..count)
+        .map(|bid| {
+            let cards = (0..5)
+                .map(|_| Card::ALL[random.below(Card::ALL.len())])
+                .collect();
+            let hand = Hand::new(CardValues::new(cards, &JokersWild), &JokersWild);
+            (hand, bid as u32)
+        })
+        .collect()
+}
+
+fn bench_sort(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sort");
+
+    for count in [1_000, 100_000, 1_000_000] {
+        let hands = hands(count);
+
+        // The comparison based sort process used before the packed keys.
+        group.bench_with_input(BenchmarkId::new("sort_by", count), &hands, |b, hands| {
+            b.iter_batched(
+                || hands.clone(),
+                |mut hands| {
+                    hands.sort_by(|(a, _), (b, _)| a.cmp(b));
+                    hands
+                },
+                BatchSize::LargeInput,
+            )
+        });
+
+        group.bench_with_input(BenchmarkId::new("packed", count), &hands, |b, hands| {
+            b.iter_batched(
+                || hands.clone(),
+                |mut hands| {
+                    sort_hands(&mut hands);
+                    hands
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_sort);
+criterion_main!(benches);
@@ -0,0 +1,35 @@
+use day7::camel::parse_hand;
+use day7::distribution::{beat_probability, Distribution};
+use day7::rules::{JokersWild, RuleSet, Standard};
+
+// distribution [--jokers] [hand]
+fn main() -> anyhow::Result<()> {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    let rules: &dyn RuleSet = if args.iter().any(|arg| arg == "--jokers") {
+        &JokersWild
+    } else {
+        &Standard
+    };
+
+    for (hand_type, probability) in Distribution::new(rules)?.entries() {
+        println!(
+            "{:>12}  {:>13}  {:.6}",
+            hand_type.name(),
+            probability.to_string(),
+            probability.as_f64()
+        );
+    }
+
+    if let Some(cards) = args.iter().find(|arg| !arg.starts_with("--")) {
+        let (hand, _) = parse_hand(&format!("{} 0", cards), rules)?;
+        let probability = beat_probability(&hand, rules)?;
+        println!(
+            "{} is beaten by {} hands, {:.6}",
+            hand,
+            probability,
+            probability.as_f64()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use day7::camel::parse_hand;
+use day7::rules::{JokersWild, RuleSet, Standard};
+
+// explain <hand> <hand> [--jokers]
+fn main() -> anyhow::Result<()> {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    let rules: &dyn RuleSet = if args.iter().any(|arg| arg == "--jokers") {
+        &JokersWild
+    } else {
+        &Standard
+    };
+
+    let hands = args
+        .iter()
+        .filter(|arg| !arg.starts_with("--"))
+        .map(|arg| parse_hand(&format!("{} 0", arg), rules).map(|(hand, _)| hand))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let [ours, theirs] = hands.as_slice() else {
+        return Err(anyhow::anyhow!("Usage: explain <hand> <hand> [--jokers]"));
+    };
+
+    println!("{}", ours.explain_cmp(theirs));
+
+    Ok(())
+}
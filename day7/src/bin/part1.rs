@@ -1,6 +1,6 @@
 use day7::part1::process;
 
-const RESULT: u32 = 250946742;
+const RESULT: u64 = 250946742;
 
 fn main() -> anyhow::Result<()> {
     let input = std::fs::read_to_string("input1.txt")?;
@@ -1,6 +1,6 @@
 use day7::part2::process;
 
-const RESULT: u32 = 251824095;
+const RESULT: u64 = 251824095;
 
 fn main() -> anyhow::Result<()> {
     let input = std::fs::read_to_string("input1.txt")?;
@@ -0,0 +1,26 @@
+use day7::report::{counts_to_csv, report, to_csv, to_json};
+use day7::rules::{JokersWild, RuleSet, Standard};
+
+// report [--jokers] [--json | --counts]
+fn main() -> anyhow::Result<()> {
+    let input = std::fs::read_to_string("input1.txt")?;
+    let args = std::env::args().collect::<Vec<String>>();
+    let flag = |name: &str| args.iter().any(|arg| arg == name);
+
+    let rules: &dyn RuleSet = if flag("--jokers") {
+        &JokersWild
+    } else {
+        &Standard
+    };
+
+    let reports = report(&input, rules)?;
+    if flag("--json") {
+        println!("{}", to_json(&reports));
+    } else if flag("--counts") {
+        print!("{}", counts_to_csv(&reports));
+    } else {
+        print!("{}", to_csv(&reports));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,703 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use anyhow::{anyhow, Result};
+
+use crate::report;
+use crate::rules::RuleSet;
+
+#[derive(Debug, PartialEq, PartialOrd, Ord, Eq, Copy, Clone, Hash)]
+pub enum Card {
+    Two = 2,
+    Three = 3,
+    Four = 4,
+    Five = 5,
+    Six = 6,
+    Seven = 7,
+    Eight = 8,
+    Nine = 9,
+    Ten = 10,
+    Jack = 11,
+    Queen = 12,
+    King = 13,
+    Ace = 14,
+}
+
+impl Card {
+    pub const ALL: [Card; 13] = [
+        Card::Two,
+        Card::Three,
+        Card::Four,
+        Card::Five,
+        Card::Six,
+        Card::Seven,
+        Card::Eight,
+        Card::Nine,
+        Card::Ten,
+        Card::Jack,
+        Card::Queen,
+        Card::King,
+        Card::Ace,
+    ];
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let card_char = match self {
+            Card::Two => '2',
+            Card::Three => '3',
+            Card::Four => '4',
+            Card::Five => '5',
+            Card::Six => '6',
+            Card::Seven => '7',
+            Card::Eight => '8',
+            Card::Nine => '9',
+            Card::Ten => 'T',
+            Card::Jack => 'J',
+            Card::Queen => 'Q',
+            Card::King => 'K',
+            Card::Ace => 'A',
+        };
+        write!(f, "{}", card_char)
+    }
+}
+
+impl TryFrom<char> for Card {
+    type Error = anyhow::Error;
+
+    fn try_from(value: char) -> Result<Self> {
+        match value {
+            '2' => Ok(Card::Two),
+            '3' => Ok(Card::Three),
+            '4' => Ok(Card::Four),
+            '5' => Ok(Card::Five),
+            '6' => Ok(Card::Six),
+            '7' => Ok(Card::Seven),
+            '8' => Ok(Card::Eight),
+            '9' => Ok(Card::Nine),
+            'T' => Ok(Card::Ten),
+            'J' => Ok(Card::Jack),
+            'Q' => Ok(Card::Queen),
+            'K' => Ok(Card::King),
+            'A' => Ok(Card::Ace),
+            _ => Err(anyhow!("Invalid card character {}", value)),
+        }
+    }
+}
+
+// The cards in the order they were dealt, along with their strength
+// under the rule set used to parse them.
+// Ordering compares the strength of the first card first, then the following
+// and so on until it founds the higher/lower. Only the strengths take part in
+// equality and ordering, cards of the same strength tie.
+#[derive(Debug, Clone)]
+pub struct CardValues {
+    strengths: Vec<u8>,
+    cards: Vec<Card>,
+}
+
+impl PartialEq for CardValues {
+    fn eq(&self, other: &Self) -> bool {
+        self.strengths == other.strengths
+    }
+}
+
+impl Eq for CardValues {}
+
+impl PartialOrd for CardValues {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CardValues {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.strengths.cmp(&other.strengths)
+    }
+}
+
+impl Hash for CardValues {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.strengths.hash(state);
+    }
+}
+
+impl CardValues {
+    pub fn new(cards: Vec<Card>, rules: &dyn RuleSet) -> Self {
+        let strengths = cards.iter().map(|&card| rules.strength(card)).collect();
+        Self { cards, strengths }
+    }
+
+    // The hand must have exactly as many cards as the rule set deals,
+    // positions in errors are 1 based.
+    pub fn parse(value: &str, rules: &dyn RuleSet) -> Result<Self> {
+        let size = rules.hand_size();
+        let found = value.chars().count();
+        if found != size {
+            return Err(anyhow!(
+                "Invalid hand {}, expected {} cards but found {}",
+                value,
+                size,
+                found
+            ));
+        }
+
+        let cards = value
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                Card::try_from(c).map_err(|_| {
+                    anyhow!(
+                        "Invalid hand {}, unknown card {} at position {}",
+                        value,
+                        c,
+                        i + 1
+                    )
+                })
+            })
+            .collect::<Result<Vec<Card>>>()?;
+
+        Ok(Self::new(cards, rules))
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn strengths(&self) -> &[u8] {
+        &self.strengths
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+}
+
+impl fmt::Display for CardValues {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let card_strings: String = self.cards.iter().map(|card| card.to_string()).collect();
+        write!(f, "{}", card_strings)
+    }
+}
+
+// From the weakest to the strongest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HandType {
+    High,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandType {
+    pub const ALL: [HandType; 7] = [
+        HandType::High,
+        HandType::OnePair,
+        HandType::TwoPair,
+        HandType::ThreeOfAKind,
+        HandType::FullHouse,
+        HandType::FourOfAKind,
+        HandType::FiveOfAKind,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HandType::High => "High",
+            HandType::OnePair => "OnePair",
+            HandType::TwoPair => "TwoPair",
+            HandType::ThreeOfAKind => "ThreeOfAKind",
+            HandType::FullHouse => "FullHouse",
+            HandType::FourOfAKind => "FourOfAKind",
+            HandType::FiveOfAKind => "FiveOfAKind",
+        }
+    }
+}
+
+// The type decides first and the cards break ties. The substitution follows
+// from the cards, so it takes no part in equality and ordering either.
+#[derive(Debug, Clone)]
+pub struct Hand {
+    hand_type: HandType,
+    cards: CardValues,
+    // the wildcard and the card it became, when the hand has wildcards
+    substitution: Option<(Card, Card)>,
+}
+
+impl PartialEq for Hand {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Hand {}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.hand_type, &self.cards).cmp(&(other.hand_type, &other.cards))
+    }
+}
+
+impl Hash for Hand {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hand_type.hash(state);
+        self.cards.hash(state);
+    }
+}
+
+impl Hand {
+    pub fn new(cards: CardValues, rules: &dyn RuleSet) -> Self {
+        resolve(cards, rules).hand
+    }
+
+    fn classify(hand: &[Card], cards: CardValues, substitution: Option<(Card, Card)>) -> Self {
+        Self {
+            hand_type: Self::rank(hand),
+            cards,
+            substitution,
+        }
+    }
+
+    // AAAAA -> Five
+    // AA8AA -> Four
+    // 23332 -> Full house
+    // TTT98 -> Three
+    // 23432 -> two
+    // A23A4 -> one
+    // 23456 -> High
+    // Other hand sizes only look at the two largest groups, so six aces are
+    // still five of a kind and AAA is three of a kind.
+    fn rank(hand: &[Card]) -> HandType {
+        // Count the occurrences of each card
+        let mut counts = [0usize; Card::ALL.len()];
+        for &card in hand.iter() {
+            counts[card as usize - Card::Two as usize] += 1;
+        }
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+
+        match (counts[0], counts[1]) {
+            (5.., _) => HandType::FiveOfAKind,
+            (4, _) => HandType::FourOfAKind,
+            (3, 2..) => HandType::FullHouse,
+            (3, _) => HandType::ThreeOfAKind,
+            (2, 2) => HandType::TwoPair,
+            (2, _) => HandType::OnePair,
+            _ => HandType::High,
+        }
+    }
+
+    pub fn hand_type(&self) -> HandType {
+        self.hand_type
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.hand_type.name()
+    }
+
+    pub fn cards(&self) -> &CardValues {
+        &self.cards
+    }
+
+    pub fn substitution(&self) -> Option<(Card, Card)> {
+        self.substitution
+    }
+
+    // The whole ordering packed in an integer, 3 bits for the type and 4 bits
+    // per card strength, first card highest. None for hands of more than 15
+    // cards or with a strength above 15, which do not fit.
+    pub fn sort_key(&self) -> Option<u64> {
+        let strengths = self.cards.strengths();
+        if strengths.len() > 15 || strengths.iter().any(|&strength| strength > 15) {
+            return None;
+        }
+        Some(
+            strengths
+                .iter()
+                .fold(self.hand_type as u64, |key, &strength| {
+                    key << 4 | strength as u64
+                }),
+        )
+    }
+}
+
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({})", self.name(), self.cards())
+    }
+}
+
+// The best hand the wildcards can make and the card they all became,
+// substitute is None when the hand has no wildcards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolution {
+    pub hand: Hand,
+    pub substitute: Option<Card>,
+    // the cards with every wildcard replaced by the substitute
+    pub substituted: Vec<Card>,
+}
+
+// Tries every card in the hand as the value of the wildcards and keeps the
+// best hand, turning all of them into the same card is never worse than
+// splitting them. Ties go to the strongest card, a hand of only wildcards
+// becomes the strongest card that is not the wildcard.
+pub fn resolve(cards: CardValues, rules: &dyn RuleSet) -> Resolution {
+    let Some(wildcard) = rules.wildcard().filter(|w| cards.cards.contains(w)) else {
+        let substituted = cards.cards.clone();
+        return Resolution {
+            hand: Hand::classify(&substituted, cards, None),
+            substitute: None,
+            substituted,
+        };
+    };
+
+    let substitute_with = |substitute: Card| -> Vec<Card> {
+        cards
+            .cards
+            .iter()
+            .map(|&card| if card == wildcard { substitute } else { card })
+            .collect()
+    };
+
+    let substitute = cards
+        .cards
+        .iter()
+        .copied()
+        .filter(|&card| card != wildcard)
+        .max_by_key(|&card| (Hand::rank(&substitute_with(card)), rules.strength(card)))
+        .or_else(|| {
+            Card::ALL
+                .into_iter()
+                .filter(|&card| card != wildcard)
+                .max_by_key(|&card| rules.strength(card))
+        })
+        .unwrap_or(wildcard);
+    let substituted = substitute_with(substitute);
+
+    Resolution {
+        hand: Hand::classify(&substituted, cards, Some((wildcard, substitute))),
+        substitute: Some(substitute),
+        substituted,
+    }
+}
+
+// Sum of rank times bid over the tournament, the same total the report
+// gives so the winnings are computed in one place.
+pub fn process(input: &str, rules: &dyn RuleSet) -> Result<u64> {
+    Ok(report::total_winnings(&report::report(input, rules)?))
+}
+
+// Weakest hand first, keeping the input order of equal hands. The packed keys
+// turn every comparison into an integer one, hands too long for a key are
+// compared directly.
+pub fn sort_hands(hands: &mut [(Hand, u32)]) {
+    if hands.iter().all(|(hand, _)| hand.sort_key().is_some()) {
+        hands.sort_by_cached_key(|(hand, _)| hand.sort_key());
+    } else {
+        hands.sort_by(|(a, _), (b, _)| a.cmp(b));
+    }
+}
+
+pub fn parse_hand(input: &str, rules: &dyn RuleSet) -> Result<(Hand, u32)> {
+    // T55J5 684
+    let mut it = input.trim().split_ascii_whitespace();
+    let card_str = it.next().ok_or(anyhow!("Invalid card input"))?;
+
+    let cards = CardValues::parse(card_str, rules)?;
+    let bid = it
+        .next()
+        .ok_or(anyhow!("Invalid bid input"))?
+        .parse::<u32>()?;
+
+    Ok((Hand::new(cards, rules), bid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::XorShift;
+    use crate::rules::{HandSize, JokersWild, Standard, Wildcard};
+
+    const INPUT: &str = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    #[test]
+    fn test_rule_sets() {
+        assert_eq!(process(INPUT, &Standard).unwrap(), 6440);
+        assert_eq!(process(INPUT, &JokersWild).unwrap(), 5905);
+        assert_eq!(process(INPUT, &Wildcard(Card::Jack)).unwrap(), 5905);
+    }
+
+    #[test]
+    fn test_custom_wildcard() {
+        let rules = Wildcard(Card::Two);
+        let (hand, _) = parse_hand("2KK2A 1", &rules).unwrap();
+        assert_eq!(hand.hand_type(), HandType::FourOfAKind);
+
+        // the wildcard is the weakest card for tie breaks
+        let (twos, _) = parse_hand("K2AAA 1", &rules).unwrap();
+        let (threes, _) = parse_hand("K3AAA 1", &rules).unwrap();
+        assert_eq!(twos.hand_type(), HandType::FourOfAKind);
+        assert!(twos > threes);
+        let (first, _) = parse_hand("2AAAA 1", &rules).unwrap();
+        let (second, _) = parse_hand("3AAAA 1", &rules).unwrap();
+        assert!(first > second);
+        let (first, _) = parse_hand("2AAAA 1", &rules).unwrap();
+        let (second, _) = parse_hand("AAAA2 1", &rules).unwrap();
+        assert!(first < second);
+    }
+
+    #[test]
+    fn test_resolve_substitute() {
+        let resolve_str = |hand: &str| {
+            let cards = CardValues::parse(hand, &JokersWild).unwrap();
+            resolve(cards, &JokersWild)
+        };
+
+        let resolution = resolve_str("KTJJT");
+        assert_eq!(resolution.hand.hand_type(), HandType::FourOfAKind);
+        assert_eq!(resolution.substitute, Some(Card::Ten));
+        assert_eq!(
+            resolution.substituted,
+            [Card::King, Card::Ten, Card::Ten, Card::Ten, Card::Ten]
+        );
+
+        // a lone joker pairs with the strongest card
+        let resolution = resolve_str("J2345");
+        assert_eq!(resolution.hand.hand_type(), HandType::OnePair);
+        assert_eq!(resolution.substitute, Some(Card::Five));
+
+        let resolution = resolve_str("JJJJJ");
+        assert_eq!(resolution.hand.hand_type(), HandType::FiveOfAKind);
+        assert_eq!(resolution.substitute, Some(Card::Ace));
+
+        // aces can not stand for themselves, kings are the next best card
+        let rules = Wildcard(Card::Ace);
+        let resolution = resolve(CardValues::parse("AAAAA", &rules).unwrap(), &rules);
+        assert_eq!(resolution.hand.hand_type(), HandType::FiveOfAKind);
+        assert_eq!(resolution.substitute, Some(Card::King));
+        assert_eq!(
+            resolution.hand.substitution(),
+            Some((Card::Ace, Card::King))
+        );
+
+        let resolution = resolve_str("32T3K");
+        assert_eq!(resolution.hand.hand_type(), HandType::OnePair);
+        assert_eq!(resolution.substitute, None);
+    }
+
+    // Every wildcard picks its own card, without assuming they all become the same one.
+    fn brute_force(cards: [Card; 5], wildcard: Card) -> HandType {
+        let jokers: Vec<usize> = (0..5).filter(|&i| cards[i] == wildcard).collect();
+        let others: Vec<Card> = Card::ALL.into_iter().filter(|&c| c != wildcard).collect();
+        let mut best = HandType::High;
+        for n in 0..others.len().pow(jokers.len() as u32) {
+            let mut substituted = cards;
+            for (j, &position) in jokers.iter().enumerate() {
+                substituted[position] = others[n / others.len().pow(j as u32) % others.len()];
+            }
+            best = best.max(Hand::rank(&substituted));
+        }
+        best
+    }
+
+    #[test]
+    fn test_resolve_exhaustive() {
+        let rules = JokersWild;
+        for n in 0..13usize.pow(5) {
+            let cards: [Card; 5] =
+                std::array::from_fn(|i| Card::ALL[n / 13usize.pow(i as u32) % 13]);
+            let values = CardValues::new(cards.to_vec(), &rules);
+            let resolution = resolve(values.clone(), &rules);
+
+            assert_eq!(
+                resolution.hand.hand_type(),
+                brute_force(cards, Card::Jack),
+                "{}",
+                values
+            );
+            // the reported substitution makes that hand
+            assert_eq!(
+                Hand::rank(&resolution.substituted),
+                resolution.hand.hand_type()
+            );
+            assert_eq!(resolution.substitute.is_some(), cards.contains(&Card::Jack));
+        }
+    }
+
+    #[test]
+    fn test_card_values_ignore_resolution() {
+        let cards = vec![Card::King, Card::Ten, Card::Jack, Card::Jack, Card::Ten];
+        let values = CardValues::new(cards, &JokersWild);
+        let (hand, _) = parse_hand("KTJJT 1", &JokersWild).unwrap();
+
+        assert_eq!(hand.cards(), &values);
+        assert_eq!(hand.substitution(), Some((Card::Jack, Card::Ten)));
+        assert_eq!(
+            parse_hand("KTJJT 1", &Standard).unwrap().0.substitution(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_strict_hand_size() {
+        let error = |hand: &str| CardValues::parse(hand, &Standard).unwrap_err().to_string();
+
+        assert_eq!(error("AK"), "Invalid hand AK, expected 5 cards but found 2");
+        assert_eq!(
+            error("AKQJT9"),
+            "Invalid hand AKQJT9, expected 5 cards but found 6"
+        );
+        assert_eq!(
+            error("AK1QJ"),
+            "Invalid hand AK1QJ, unknown card 1 at position 3"
+        );
+        assert_eq!(
+            error("AKQJé"),
+            "Invalid hand AKQJé, unknown card é at position 5"
+        );
+        assert!(parse_hand("AK 10", &Standard).is_err());
+    }
+
+    #[test]
+    fn test_custom_hand_size() {
+        let three = HandSize(Standard, 3);
+        let hand = |input: &str, rules: &dyn RuleSet| parse_hand(input, rules).unwrap().0;
+
+        assert_eq!(hand("AAA 1", &three).hand_type(), HandType::ThreeOfAKind);
+        assert_eq!(hand("AKA 1", &three).hand_type(), HandType::OnePair);
+        assert!(parse_hand("AKQJT 1", &three).is_err());
+
+        let seven = HandSize(JokersWild, 7);
+        assert_eq!(hand("AAJKKQ2 1", &seven).hand_type(), HandType::FullHouse);
+        assert_eq!(hand("AAAJJKK 1", &seven).hand_type(), HandType::FiveOfAKind);
+        assert_eq!(hand("AAJKKQ2 1", &seven).cards().len(), 7);
+
+        assert_eq!(process("AAA 1\nAKA 2\n23T 3", &three).unwrap(), 3 + 4 + 3);
+    }
+
+    #[test]
+    fn test_process_large_bids() {
+        // 3 * u32::MAX does not fit the u32 the winnings used to be summed in
+        let input = "23456 4294967295\nAAAAA 4294967295";
+        assert_eq!(process(input, &Standard).unwrap(), 3 * u32::MAX as u64);
+        assert_eq!(
+            process(input, &Standard).unwrap(),
+            report::total_winnings(&report::report(input, &Standard).unwrap())
+        );
+    }
+
+    // Random hands, with repeats so equal hands show up.
+    fn random_hands(count: usize, rules: &dyn RuleSet) -> Vec<(Hand, u32)> {
+        let mut random = XorShift::default();
+        (0..count)
+            .map(|bid| {
+                let cards = (0..5)
+                    .map(|_| Card::ALL[random.below(4) + random.below(10)])
+                    .collect();
+                (Hand::new(CardValues::new(cards, rules), rules), bid as u32)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_total_order() {
+        let hands = random_hands(80, &JokersWild);
+
+        for (a, _) in hands.iter() {
+            for (b, _) in hands.iter() {
+                let ordering = a.cmp(b);
+                assert_eq!(ordering, b.cmp(a).reverse());
+                assert_eq!(ordering.is_eq(), a == b);
+                assert_eq!(a.partial_cmp(b), Some(ordering));
+                assert_eq!(a.sort_key().cmp(&b.sort_key()), ordering);
+
+                for (c, _) in hands.iter() {
+                    if a <= b && b <= c {
+                        assert!(a <= c, "{} {} {}", a, b, c);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_equal_strengths_tie() {
+        // kings are no stronger than queens
+        struct Flat;
+        impl RuleSet for Flat {
+            fn strength(&self, card: Card) -> u8 {
+                match card {
+                    Card::King => Card::Queen as u8,
+                    card => card as u8,
+                }
+            }
+        }
+        let hand = |input: &str| parse_hand(input, &Flat).unwrap().0;
+
+        assert_eq!(hand("QQ234 1"), hand("KK234 1"));
+        assert_eq!(hand("QQ234 1").cmp(&hand("KK234 1")), Ordering::Equal);
+        assert_eq!(hand("QQ234 1").sort_key(), hand("KK234 1").sort_key());
+        assert!(hand("KK234 1") < hand("AA234 1"));
+
+        let mut hands = random_hands(2_000, &Flat);
+        let mut expected = hands.clone();
+        expected.sort_by(|(a, _), (b, _)| a.cmp(b));
+        sort_hands(&mut hands);
+        let bids = |hands: &[(Hand, u32)]| hands.iter().map(|&(_, bid)| bid).collect::<Vec<_>>();
+        assert_eq!(bids(&hands), bids(&expected));
+    }
+
+    #[test]
+    fn test_sort_hands() {
+        for rules in [&Standard as &dyn RuleSet, &JokersWild] {
+            let hands = random_hands(20_000, rules);
+
+            let mut expected = hands.clone();
+            expected.sort_by(|(a, _), (b, _)| a.cmp(b));
+            let mut sorted = hands;
+            sort_hands(&mut sorted);
+            // same hands and bids, equal hands keep their input order
+            assert_eq!(sorted, expected);
+        }
+
+        // too many cards for a key
+        let rules = HandSize(Standard, 16);
+        let (long, _) = parse_hand("AAAAAAAAAAAAAAAA 1", &rules).unwrap();
+        assert_eq!(long.sort_key(), None);
+        assert_eq!(
+            process("AAAAAAAAAAAAAAAA 2\n23456789TJQKA234 3", &rules).unwrap(),
+            3 + 4
+        );
+
+        // strengths too large for a nibble
+        struct Spread;
+        impl RuleSet for Spread {
+            fn strength(&self, card: Card) -> u8 {
+                card as u8 * 10
+            }
+        }
+        let hands = random_hands(2_000, &Spread);
+        assert!(hands.iter().all(|(hand, _)| hand.sort_key().is_none()));
+        let mut expected = hands.clone();
+        expected.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let mut sorted = hands;
+        sort_hands(&mut sorted);
+        assert_eq!(sorted, expected);
+    }
+}
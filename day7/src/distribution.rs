@@ -0,0 +1,245 @@
+use std::fmt;
+
+use anyhow::{anyhow, Result};
+
+use crate::camel::{Card, CardValues, Hand, HandType};
+use crate::rules::RuleSet;
+
+// An exact probability, `hits` out of `total` equally likely hands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Probability {
+    pub hits: u64,
+    pub total: u64,
+}
+
+impl Probability {
+    pub fn as_f64(&self) -> f64 {
+        self.hits as f64 / self.total as f64
+    }
+}
+
+impl fmt::Display for Probability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.hits, self.total)
+    }
+}
+
+// How many of the ordered hands the rule set can deal are of each type,
+// counts are indexed by HandType.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Distribution {
+    pub counts: [u64; 7],
+    pub total: u64,
+}
+
+impl Distribution {
+    pub fn new(rules: &dyn RuleSet) -> Result<Self> {
+        let size = rules.hand_size();
+        let total = total_hands(size)?;
+        let counts = completions(&[0; CARDS], size, rules);
+
+        Ok(Self { counts, total })
+    }
+
+    pub fn probability(&self, hand_type: HandType) -> Probability {
+        Probability {
+            hits: self.counts[hand_type as usize],
+            total: self.total,
+        }
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (HandType, Probability)> + '_ {
+        HandType::ALL
+            .into_iter()
+            .map(|hand_type| (hand_type, self.probability(hand_type)))
+    }
+}
+
+// Chance that a random hand dealt under the same rules ranks above `hand`.
+// Hands of a stronger type always do, hands of the same type do when the
+// first card they differ on is stronger, so for every position the cards
+// before it are fixed and only the remaining ones are counted.
+pub fn beat_probability(hand: &Hand, rules: &dyn RuleSet) -> Result<Probability> {
+    let size = hand.cards().len();
+    let total = total_hands(size)?;
+    let hand_type = hand.hand_type();
+
+    let mut hits: u64 = completions(&[0; CARDS], size, rules)[hand_type as usize + 1..]
+        .iter()
+        .sum();
+
+    let mut prefix = [0; CARDS];
+    for (i, &card) in hand.cards().cards().iter().enumerate() {
+        for stronger in Card::ALL
+            .into_iter()
+            .filter(|&other| rules.strength(other) > rules.strength(card))
+        {
+            prefix[index(stronger)] += 1;
+            hits += completions(&prefix, size - i - 1, rules)[hand_type as usize];
+            prefix[index(stronger)] -= 1;
+        }
+        prefix[index(card)] += 1;
+    }
+
+    Ok(Probability { hits, total })
+}
+
+const CARDS: usize = Card::ALL.len();
+
+fn index(card: Card) -> usize {
+    card as usize - Card::Two as usize
+}
+
+// 13^size, the number of ordered hands.
+fn total_hands(size: usize) -> Result<u64> {
+    u32::try_from(size)
+        .ok()
+        .and_then(|size| (CARDS as u64).checked_pow(size))
+        .ok_or(anyhow!("Hands of {} cards are too many to count", size))
+}
+
+// Number of ways to deal `size` more cards after the fixed `prefix`, by the
+// type of the resulting hand. The type only depends on how many of each card
+// the hand has, so every multiset of cards is resolved once and weighted by
+// the number of orders it can be dealt in.
+fn completions(prefix: &[usize; CARDS], size: usize, rules: &dyn RuleSet) -> [u64; 7] {
+    let mut counts = [0; 7];
+    let mut rest = [0; CARDS];
+    walk(0, size, 1, &mut rest, &mut |rest, orders| {
+        let cards = (0..CARDS)
+            .flat_map(|i| std::iter::repeat_n(Card::ALL[i], prefix[i] + rest[i]))
+            .collect();
+        let hand_type = Hand::new(CardValues::new(cards, rules), rules).hand_type();
+        counts[hand_type as usize] += orders;
+    });
+    counts
+}
+
+// Every way to spread `left` cards over the cards from `card` on, `orders`
+// is the number of distinct orders of the cards placed so far.
+fn walk(
+    card: usize,
+    left: usize,
+    orders: u64,
+    rest: &mut [usize; CARDS],
+    visit: &mut dyn FnMut(&[usize; CARDS], u64),
+) {
+    if card == CARDS - 1 {
+        rest[card] = left;
+        visit(rest, orders);
+        return;
+    }
+
+    // choosing the positions of this card among the ones still free
+    let mut positions = 1;
+    for count in 0..=left {
+        rest[card] = count;
+        walk(card + 1, left - count, orders * positions, rest, visit);
+        positions = positions * (left - count) as u64 / (count + 1) as u64;
+    }
+    rest[card] = 0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camel::parse_hand;
+    use crate::rules::{HandSize, JokersWild, Standard};
+
+    #[test]
+    fn test_standard_distribution() {
+        let distribution = Distribution::new(&Standard).unwrap();
+        assert_eq!(distribution.total, 13u64.pow(5));
+        // 13 * 12 * 11 * 10 * 9 distinct cards, 13 * C(12, 3) * 5! / 2! one pair...
+        assert_eq!(
+            distribution.counts,
+            [154440, 171600, 25740, 17160, 1560, 780, 13]
+        );
+        assert_eq!(
+            distribution.probability(HandType::FiveOfAKind),
+            Probability {
+                hits: 13,
+                total: 371293
+            }
+        );
+    }
+
+    #[test]
+    fn test_jokers_distribution() {
+        let distribution = Distribution::new(&JokersWild).unwrap();
+        assert_eq!(distribution.counts.iter().sum::<u64>(), 13u64.pow(5));
+        // one other card filling the rest with jokers, plus JJJJJ
+        assert_eq!(distribution.counts[6], 12 * 31 + 1);
+        // jokers only ever move hands up
+        let standard = Distribution::new(&Standard).unwrap();
+        assert!(distribution.counts[0] < standard.counts[0]);
+        assert!(distribution.counts[6] > standard.counts[6]);
+
+        let small = Distribution::new(&HandSize(Standard, 2)).unwrap();
+        assert_eq!(small.counts[..2], [156, 13]);
+    }
+
+    #[test]
+    fn test_too_many_hands() {
+        assert!(Distribution::new(&HandSize(Standard, 18)).is_err());
+        let rules = HandSize(Standard, 18);
+        let hand = parse_hand("23456789TJQKA23456 1", &rules).unwrap().0;
+        assert!(beat_probability(&hand, &rules).is_err());
+    }
+
+    // Deals every ordered hand of three cards and checks the counts.
+    #[test]
+    fn test_against_every_hand() {
+        let rules = HandSize(JokersWild, 3);
+        let hands = Card::ALL.into_iter().flat_map(|a| {
+            Card::ALL
+                .into_iter()
+                .flat_map(move |b| Card::ALL.into_iter().map(move |c| vec![a, b, c]))
+        });
+        let hands = hands
+            .map(|cards| Hand::new(CardValues::new(cards, &rules), &rules))
+            .collect::<Vec<_>>();
+
+        let mut counts = [0; 7];
+        for hand in hands.iter() {
+            counts[hand.hand_type() as usize] += 1;
+        }
+        assert_eq!(Distribution::new(&rules).unwrap().counts, counts);
+
+        for hand in hands.iter().step_by(37) {
+            let hits = hands.iter().filter(|other| *other > hand).count() as u64;
+            assert_eq!(
+                beat_probability(hand, &rules).unwrap().hits,
+                hits,
+                "{}",
+                hand
+            );
+        }
+    }
+
+    #[test]
+    fn test_beat_probability() {
+        let hand = |input: &str| parse_hand(input, &Standard).unwrap().0;
+        let beat = |input: &str| beat_probability(&hand(input), &Standard).unwrap();
+
+        assert_eq!(beat("AAAAA 1").hits, 0);
+        assert_eq!(
+            beat("23456 1"),
+            Probability {
+                hits: 371292,
+                total: 371293
+            }
+        );
+        // only AAAAA is above KKKKK
+        let kings = beat("KKKKK 1");
+        assert_eq!(kings.hits, 1);
+        assert!((kings.as_f64() - 1.0 / 371293.0).abs() < 1e-12);
+
+        // jokers are the weakest tie break, every other five of a kind wins
+        let jokers = parse_hand("JJJJJ 1", &JokersWild).unwrap().0;
+        assert_eq!(
+            beat_probability(&jokers, &JokersWild).unwrap().hits,
+            12 * 31
+        );
+    }
+}
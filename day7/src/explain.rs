@@ -0,0 +1,196 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::camel::{Card, Hand, HandType};
+
+// A hand type along with the wildcard and the card it became, if any.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypeUsed {
+    pub hand_type: HandType,
+    pub substitution: Option<(Card, Card)>,
+}
+
+// Why one hand ranks above, below or the same as another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reason {
+    HandType {
+        ours: TypeUsed,
+        theirs: TypeUsed,
+    },
+    // same type, the tie break walk stopped at this 1 based position
+    Card {
+        position: usize,
+        ours: Card,
+        theirs: Card,
+    },
+    Equal,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub ours: String,
+    pub theirs: String,
+    pub ordering: Ordering,
+    pub reason: Reason,
+}
+
+impl Hand {
+    // Both hands must come from the same rule set, as for cmp.
+    pub fn explain_cmp(&self, other: &Hand) -> Explanation {
+        let ordering = self.cmp(other);
+        let type_used = |hand: &Hand| TypeUsed {
+            hand_type: hand.hand_type(),
+            substitution: hand.substitution(),
+        };
+
+        let reason = if self.hand_type() != other.hand_type() {
+            Reason::HandType {
+                ours: type_used(self),
+                theirs: type_used(other),
+            }
+        } else {
+            let (ours, theirs) = (self.cards(), other.cards());
+            ours.strengths()
+                .iter()
+                .zip(theirs.strengths())
+                .position(|(a, b)| a != b)
+                .map_or(Reason::Equal, |i| Reason::Card {
+                    position: i + 1,
+                    ours: ours.cards()[i],
+                    theirs: theirs.cards()[i],
+                })
+        };
+
+        Explanation {
+            ours: self.cards().to_string(),
+            theirs: other.cards().to_string(),
+            ordering,
+            reason,
+        }
+    }
+}
+
+impl fmt::Display for TypeUsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.substitution {
+            Some((wildcard, card)) => {
+                write!(f, "{} ({} as {})", self.hand_type.name(), wildcard, card)
+            }
+            None => write!(f, "{}", self.hand_type.name()),
+        }
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verb = match self.ordering {
+            Ordering::Greater => "beats",
+            Ordering::Less => "loses to",
+            Ordering::Equal => "ties with",
+        };
+        write!(f, "{} {} {}: ", self.ours, verb, self.theirs)?;
+
+        match &self.reason {
+            Reason::HandType { ours, theirs } => write!(f, "{} {} {}", ours, verb, theirs),
+            Reason::Card {
+                position,
+                ours,
+                theirs,
+            } => write!(
+                f,
+                "same type, card {} is {} against {}",
+                position, ours, theirs
+            ),
+            Reason::Equal => write!(f, "same type and cards"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camel::parse_hand;
+    use crate::rules::{JokersWild, Standard, Wildcard};
+
+    #[test]
+    fn test_explain_cmp() {
+        let hand = |input: &str| parse_hand(input, &JokersWild).unwrap().0;
+
+        let explanation = hand("KTJJT 1").explain_cmp(&hand("QQQJA 1"));
+        assert_eq!(explanation.ordering, Ordering::Greater);
+        assert_eq!(
+            explanation.reason,
+            Reason::Card {
+                position: 1,
+                ours: Card::King,
+                theirs: Card::Queen
+            }
+        );
+        assert_eq!(
+            explanation.to_string(),
+            "KTJJT beats QQQJA: same type, card 1 is K against Q"
+        );
+
+        let explanation = hand("T55J5 1").explain_cmp(&hand("KK677 1"));
+        assert_eq!(
+            explanation.to_string(),
+            "T55J5 beats KK677: FourOfAKind (J as 5) beats TwoPair"
+        );
+        let explanation = hand("KK677 1").explain_cmp(&hand("T55J5 1"));
+        assert_eq!(explanation.ordering, Ordering::Less);
+        assert_eq!(
+            explanation.to_string(),
+            "KK677 loses to T55J5: TwoPair loses to FourOfAKind (J as 5)"
+        );
+
+        // the joker is the weakest card in the walk
+        let explanation = hand("JKKK2 1").explain_cmp(&hand("QQQQ2 1"));
+        assert_eq!(
+            explanation.to_string(),
+            "JKKK2 loses to QQQQ2: same type, card 1 is J against Q"
+        );
+
+        let explanation = hand("23456 1").explain_cmp(&hand("23456 2"));
+        assert_eq!(explanation.ordering, Ordering::Equal);
+        assert_eq!(explanation.reason, Reason::Equal);
+    }
+
+    #[test]
+    fn test_explain_custom_wildcard() {
+        let rules = Wildcard(Card::Two);
+        let hand = |input: &str| parse_hand(input, &rules).unwrap().0;
+
+        assert_eq!(
+            hand("2AA3A 1").explain_cmp(&hand("KKQQ3 1")).to_string(),
+            "2AA3A beats KKQQ3: FourOfAKind (2 as A) beats TwoPair"
+        );
+
+        let rules = Wildcard(Card::Ace);
+        let hand = |input: &str| parse_hand(input, &rules).unwrap().0;
+        assert_eq!(
+            hand("AAAAA 1").explain_cmp(&hand("2345A 1")).to_string(),
+            "AAAAA beats 2345A: FiveOfAKind (A as K) beats OnePair (A as 5)"
+        );
+    }
+
+    #[test]
+    fn test_explain_agrees_with_cmp() {
+        let input = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+        let hands = input
+            .lines()
+            .map(|line| parse_hand(line, &Standard).unwrap().0)
+            .collect::<Vec<Hand>>();
+
+        for a in hands.iter() {
+            for b in hands.iter() {
+                let explanation = a.explain_cmp(b);
+                assert_eq!(explanation.ordering, a.cmp(b));
+                assert_eq!(explanation.reason == Reason::Equal, a == b);
+            }
+        }
+    }
+}
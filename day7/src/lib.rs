@@ -1,3 +1,11 @@
+pub mod camel;
+pub mod distribution;
+pub mod explain;
 pub mod part1;
 pub mod part2;
+pub mod report;
+pub mod rules;
 
+#[cfg(test)]
+#[path = "../benches/common/mod.rs"]
+mod common;
@@ -0,0 +1,188 @@
+use anyhow::Result;
+
+use crate::camel::{parse_hand, sort_hands, Hand, HandType};
+use crate::rules::RuleSet;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandReport {
+    pub rank: usize,
+    pub cards: String,
+    pub hand_type: HandType,
+    pub bid: u32,
+    pub winnings: u64,
+}
+
+// Every hand of the tournament, the weakest one first with rank 1.
+pub fn report(input: &str, rules: &dyn RuleSet) -> Result<Vec<HandReport>> {
+    let mut hands = input
+        .lines()
+        .map(|line| parse_hand(line, rules))
+        .collect::<Result<Vec<(Hand, u32)>>>()?;
+    sort_hands(&mut hands);
+
+    Ok(hands
+        .into_iter()
+        .enumerate()
+        .map(|(index, (hand, bid))| HandReport {
+            rank: index + 1,
+            cards: hand.cards().to_string(),
+            hand_type: hand.hand_type(),
+            bid,
+            winnings: (index as u64 + 1) * bid as u64,
+        })
+        .collect())
+}
+
+pub fn total_winnings(reports: &[HandReport]) -> u64 {
+    reports.iter().map(|report| report.winnings).sum()
+}
+
+// How many hands of each type, from the weakest to the strongest.
+pub fn type_counts(reports: &[HandReport]) -> [(HandType, usize); 7] {
+    HandType::ALL.map(|hand_type| {
+        let count = reports
+            .iter()
+            .filter(|report| report.hand_type == hand_type)
+            .count();
+        (hand_type, count)
+    })
+}
+
+impl HandReport {
+    pub fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{}",
+            self.rank,
+            self.cards,
+            self.hand_type.name(),
+            self.bid,
+            self.winnings
+        )
+    }
+
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"rank\":{},\"cards\":\"{}\",\"type\":\"{}\",\"bid\":{},\"winnings\":{}}}",
+            self.rank,
+            self.cards,
+            self.hand_type.name(),
+            self.bid,
+            self.winnings
+        )
+    }
+}
+
+pub fn to_csv(reports: &[HandReport]) -> String {
+    std::iter::once("rank,cards,type,bid,winnings".to_string())
+        .chain(reports.iter().map(HandReport::to_csv))
+        .map(|line| line + "\n")
+        .collect()
+}
+
+pub fn counts_to_csv(reports: &[HandReport]) -> String {
+    std::iter::once("type,count".to_string())
+        .chain(
+            type_counts(reports)
+                .iter()
+                .map(|(hand_type, count)| format!("{},{}", hand_type.name(), count)),
+        )
+        .map(|line| line + "\n")
+        .collect()
+}
+
+// The hands along with the per type counts and the total winnings.
+pub fn to_json(reports: &[HandReport]) -> String {
+    let hands = reports
+        .iter()
+        .map(HandReport::to_json)
+        .collect::<Vec<String>>();
+    let counts = type_counts(reports)
+        .iter()
+        .map(|(hand_type, count)| format!("\"{}\":{}", hand_type.name(), count))
+        .collect::<Vec<String>>();
+    format!(
+        "{{\"hands\":[{}],\"counts\":{{{}}},\"total_winnings\":{}}}",
+        hands.join(","),
+        counts.join(","),
+        total_winnings(reports)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{JokersWild, Standard};
+
+    const INPUT: &str = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    #[test]
+    fn test_report() {
+        let reports = report(INPUT, &Standard).unwrap();
+        let ranked = reports
+            .iter()
+            .map(|report| report.cards.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(ranked, ["32T3K", "KTJJT", "KK677", "T55J5", "QQQJA"]);
+        assert_eq!(
+            reports[1],
+            HandReport {
+                rank: 2,
+                cards: "KTJJT".to_string(),
+                hand_type: HandType::TwoPair,
+                bid: 220,
+                winnings: 440,
+            }
+        );
+        assert_eq!(total_winnings(&reports), 6440);
+
+        let reports = report(INPUT, &JokersWild).unwrap();
+        assert_eq!(reports[4].cards, "KTJJT");
+        assert_eq!(reports[4].hand_type, HandType::FourOfAKind);
+        assert_eq!(total_winnings(&reports), 5905);
+        assert_eq!(
+            type_counts(&reports),
+            [
+                (HandType::High, 0),
+                (HandType::OnePair, 1),
+                (HandType::TwoPair, 1),
+                (HandType::ThreeOfAKind, 0),
+                (HandType::FullHouse, 0),
+                (HandType::FourOfAKind, 3),
+                (HandType::FiveOfAKind, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_formats() {
+        let reports = report("AAAAA 2\n23456 5", &Standard).unwrap();
+
+        assert_eq!(
+            to_csv(&reports),
+            "rank,cards,type,bid,winnings
+1,23456,High,5,5
+2,AAAAA,FiveOfAKind,2,4
+"
+        );
+        assert_eq!(
+            counts_to_csv(&reports),
+            "type,count
+High,1
+OnePair,0
+TwoPair,0
+ThreeOfAKind,0
+FullHouse,0
+FourOfAKind,0
+FiveOfAKind,1
+"
+        );
+        assert_eq!(
+            to_json(&reports[..1]),
+            "{\"hands\":[{\"rank\":1,\"cards\":\"23456\",\"type\":\"High\",\"bid\":5,\"winnings\":5}],\"counts\":{\"High\":1,\"OnePair\":0,\"TwoPair\":0,\"ThreeOfAKind\":0,\"FullHouse\":0,\"FourOfAKind\":0,\"FiveOfAKind\":0},\"total_winnings\":5}"
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use crate::camel::Card;
+
+// What changes between Camel Cards variants: how strong each card is when
+// breaking ties, which card, if any, can stand for any other and how many
+// cards make a hand.
+pub trait RuleSet {
+    // Should be unique per card, two cards of the same strength tie when
+    // breaking ties between hands. Strengths up to 15 let hands sort on packed
+    // integer keys, larger ones fall back to comparing the hands.
+    fn strength(&self, card: Card) -> u8;
+
+    fn wildcard(&self) -> Option<Card> {
+        None
+    }
+
+    fn hand_size(&self) -> usize {
+        5
+    }
+}
+
+// Part 1 rules, no wildcard and jacks rank between ten and queen.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Standard;
+
+impl RuleSet for Standard {
+    fn strength(&self, card: Card) -> u8 {
+        card as u8
+    }
+}
+
+// Any card can be the wildcard, it becomes the weakest card for tie breaks.
+#[derive(Debug, Clone, Copy)]
+pub struct Wildcard(pub Card);
+
+impl RuleSet for Wildcard {
+    fn strength(&self, card: Card) -> u8 {
+        if card == self.0 {
+            1
+        } else {
+            card as u8
+        }
+    }
+
+    fn wildcard(&self) -> Option<Card> {
+        Some(self.0)
+    }
+}
+
+// Part 2 rules, jacks are jokers.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JokersWild;
+
+impl RuleSet for JokersWild {
+    fn strength(&self, card: Card) -> u8 {
+        Wildcard(Card::Jack).strength(card)
+    }
+
+    fn wildcard(&self) -> Option<Card> {
+        Some(Card::Jack)
+    }
+}
+
+// Any rule set dealing a different number of cards per hand.
+#[derive(Debug, Clone, Copy)]
+pub struct HandSize<R>(pub R, pub usize);
+
+impl<R: RuleSet> RuleSet for HandSize<R> {
+    fn strength(&self, card: Card) -> u8 {
+        self.0.strength(card)
+    }
+
+    fn wildcard(&self) -> Option<Card> {
+        self.0.wildcard()
+    }
+
+    fn hand_size(&self) -> usize {
+        self.1
+    }
+}